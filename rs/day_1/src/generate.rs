@@ -0,0 +1,38 @@
+use solution::Rng;
+
+/// Generates `size` sonar sweep depths, drifting deeper like the real ones.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut depth = rng.range(100..=200);
+
+    (0..size)
+        .map(|_| {
+            depth = (depth + rng.range(-15..=25)).max(0);
+            format!("{}\n", depth)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day1};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day1.parse(&generate(seed, Day1::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day1::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
@@ -1,71 +1,116 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<usize>;
 
-pub const PART_1: usize = 1374;
-pub const PART_2: usize = 1418;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day1.parse_file(Path::new(data_dir).join("day_1.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_1.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_1.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day1.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    contents
-        .lines()
-        .map(|l| l.parse::<usize>().unwrap())
-        .collect()
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    contents.lines().map(|l| source.parse::<usize>(l)).collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     input
         .windows(2)
         .filter(|window| window[1] > window[0])
         .count()
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     input
         .windows(3)
         .zip(input[1..].windows(3))
         .filter(|(old, new)| new.iter().sum::<usize>() > old.iter().sum())
         .count()
+        .into()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Input;
+
+    const DAY: u8 = 1;
+    const INPUT_SIZE: usize = 2000;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day1::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::new())
-            })
+            assert_ne!(data, Input::new())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::new())
         }
     }
 
@@ -94,8 +139,38 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:1: expected usize, found \"2O0\"",
+                parse_contents(
+                    "199
+2O0
+208"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
+        #[test]
+        fn malformed_after_blank_lines() {
+            assert_eq!(
+                "3:1: expected usize, found \"2O0\"",
+                Day1.parse(
+                    "
+199
+2O0
+208
+"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -104,25 +179,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 7,
+                expected: Answer::Integer(7),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day1::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -135,25 +210,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 5,
+                expected: Answer::Integer(5),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day1::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,69 @@
+use solution::Rng;
+
+const OPENING: &[u8] = b"([{<";
+const CLOSING: &[u8] = b")]}>";
+
+/// How deeply chunks nest. Completion scores grow fivefold with each unclosed
+/// chunk, so this keeps them well within a `usize`.
+const MAX_DEPTH: usize = 20;
+
+/// Generates `size` lines of navigation subsystem syntax, each either corrupted
+/// or incomplete. The first line is always incomplete, so there is always a
+/// completion score to find the middle of.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|idx| {
+            let len = rng.range(80..=110) as usize;
+            let mut corrupt = idx > 0 && rng.ratio(1, 2);
+            let mut open = Vec::new();
+            let mut line = String::with_capacity(len + 1);
+
+            while line.len() < len || open.is_empty() {
+                if open.is_empty() || (open.len() < MAX_DEPTH && rng.ratio(1, 2)) {
+                    let bracket = rng.below(OPENING.len());
+                    open.push(bracket);
+                    line.push(OPENING[bracket] as char);
+                    continue;
+                }
+
+                let bracket = open.pop().unwrap();
+                if corrupt && rng.ratio(1, 10) {
+                    let wrong = (bracket + rng.range(1..=3) as usize) % CLOSING.len();
+                    line.push(CLOSING[wrong] as char);
+                    corrupt = false;
+                } else {
+                    line.push(CLOSING[bracket] as char);
+                }
+            }
+
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day10};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day10.parse(&generate(seed, Day10::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day10::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
@@ -1,57 +1,70 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path, slice::Iter};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{path::Path, slice::Iter};
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Vec<Char>>;
 
-pub const PART_1: usize = 266301;
-pub const PART_2: usize = 3404870164;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day10.parse_file(Path::new(data_dir).join("day_10.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_10.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_10.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day10.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
         .map(|l| {
-            l.chars()
-                .map(|c| match c {
-                    '(' => Char::L(Bracket::Parenthesis),
-                    '[' => Char::L(Bracket::Square),
-                    '{' => Char::L(Bracket::Curly),
-                    '<' => Char::L(Bracket::Angle),
-                    ')' => Char::R(Bracket::Parenthesis),
-                    ']' => Char::R(Bracket::Square),
-                    '}' => Char::R(Bracket::Curly),
-                    '>' => Char::R(Bracket::Angle),
-                    _ => panic!("invalid character in input: {}", c),
+            l.char_indices()
+                .map(|(idx, c)| match c {
+                    '(' => Ok(Char::L(Bracket::Parenthesis)),
+                    '[' => Ok(Char::L(Bracket::Square)),
+                    '{' => Ok(Char::L(Bracket::Curly)),
+                    '<' => Ok(Char::L(Bracket::Angle)),
+                    ')' => Ok(Char::R(Bracket::Parenthesis)),
+                    ']' => Ok(Char::R(Bracket::Square)),
+                    '}' => Ok(Char::R(Bracket::Curly)),
+                    '>' => Ok(Char::R(Bracket::Angle)),
+                    _ => Err(source.error(&l[idx..], "a bracket")),
                 })
                 .collect()
         })
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.iter().fold(0usize, |acc, line| {
-        if let Err(Code::Illegal(bracket)) = validate_line(&mut line.iter(), None) {
-            match bracket {
-                Bracket::Parenthesis => acc + 3,
-                Bracket::Square => acc + 57,
-                Bracket::Curly => acc + 1197,
-                Bracket::Angle => acc + 25137,
+pub fn part_1(input: &Input) -> Answer {
+    input
+        .iter()
+        .fold(0usize, |acc, line| {
+            if let Err(Code::Illegal(bracket)) = validate_line(&mut line.iter(), None) {
+                match bracket {
+                    Bracket::Parenthesis => acc + 3,
+                    Bracket::Square => acc + 57,
+                    Bracket::Curly => acc + 1197,
+                    Bracket::Angle => acc + 25137,
+                }
+            } else {
+                acc
             }
-        } else {
-            acc
-        }
-    })
+        })
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let mut scores = input.iter().fold(Vec::new(), |mut acc, line| {
         let score = match fix_line(&mut line.iter(), None) {
             Ok(fix) => fix.iter().fold(0usize, |acc, &c| {
@@ -72,7 +85,37 @@ pub fn part_2(input: &Input) -> usize {
     });
 
     scores.sort_unstable();
-    scores[scores.len() / 2]
+    scores[scores.len() / 2].into()
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Input;
+
+    const DAY: u8 = 10;
+    const INPUT_SIZE: usize = 98;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -102,11 +145,7 @@ fn validate_line(line: &mut Iter<Char>, prev: Option<Bracket>) -> Result<(), Cod
             Some(&c) => c,
         };
         match c {
-            Char::L(b) => {
-                if let Err(code) = validate_line(line, Some(b)) {
-                    return Err(code);
-                }
-            }
+            Char::L(b) => validate_line(line, Some(b))?,
             Char::R(Bracket::Parenthesis) if prev == Some(Bracket::Parenthesis) => return Ok(()),
             Char::R(Bracket::Square) if prev == Some(Bracket::Square) => return Ok(()),
             Char::R(Bracket::Curly) if prev == Some(Bracket::Curly) => return Ok(()),
@@ -153,32 +192,38 @@ fn fix_line(line: &mut Iter<Char>, prev: Option<Bracket>) -> Result<Vec<Bracket>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day10::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -207,8 +252,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:23: expected a bracket, found \"a\"",
+                parse_contents(
+                    "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(a"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -217,25 +275,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 26397,
+                expected: Answer::Integer(26397),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day10::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -248,25 +306,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 288957,
+                expected: Answer::Integer(288957),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day10::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
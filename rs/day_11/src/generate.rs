@@ -0,0 +1,100 @@
+use solution::Rng;
+
+/// How many steps a generated grid may take to synchronise.
+const MAX_STEPS: usize = 1000;
+
+/// How many random grids are drawn before falling back to one that is sure to
+/// synchronise.
+const MAX_GRIDS: usize = 100;
+
+/// Generates a 10 by 10 grid of octopus energy levels. The puzzle's grids are
+/// always that size, and the parser rejects any other, so `size` must be 10.
+///
+/// About half of all random grids never synchronise, so grids are drawn until
+/// one does within [`MAX_STEPS`] steps, as the real inputs all do. If none of
+/// [`MAX_GRIDS`] do, every octopus starts with the same energy level, which
+/// synchronises on the first flash.
+pub fn generate(seed: u64, size: usize) -> String {
+    assert_eq!(10, size, "day 11's grids are always 10 by 10");
+    let mut rng = Rng::new(seed);
+
+    let grid = (0..MAX_GRIDS)
+        .map(|_| {
+            (0..size * size)
+                .map(|_| rng.below(10) as u8)
+                .collect::<Vec<_>>()
+        })
+        .find(|grid| synchronises(grid.clone(), size))
+        .unwrap_or_else(|| vec![rng.below(10) as u8; size * size]);
+
+    grid.chunks(size)
+        .map(|row| {
+            let mut row = row.iter().map(|&n| (b'0' + n) as char).collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// Whether every octopus flashes in the same step within [`MAX_STEPS`] steps.
+fn synchronises(mut grid: Vec<u8>, size: usize) -> bool {
+    for _ in 0..MAX_STEPS {
+        let mut flashing = Vec::new();
+        for (idx, n) in grid.iter_mut().enumerate() {
+            *n += 1;
+            if *n == 10 {
+                flashing.push(idx);
+            }
+        }
+
+        let mut flashes = 0;
+        while let Some(idx) = flashing.pop() {
+            flashes += 1;
+            let (x, y) = (idx % size, idx / size);
+            for ny in y.saturating_sub(1)..=(y + 1).min(size - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(size - 1) {
+                    let n = &mut grid[ny * size + nx];
+                    *n += 1;
+                    if *n == 10 {
+                        flashing.push(ny * size + nx);
+                    }
+                }
+            }
+        }
+
+        grid.iter_mut().filter(|n| **n > 9).for_each(|n| *n = 0);
+        if flashes == grid.len() {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day11};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 10), generate(1, 10));
+        assert_ne!(generate(1, 10), generate(2, 10));
+    }
+
+    #[test]
+    #[should_panic(expected = "always 10 by 10")]
+    fn other_sizes() {
+        generate(1, 50);
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day11.parse(&generate(seed, Day11::INPUT_SIZE)).unwrap();
+
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
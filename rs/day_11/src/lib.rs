@@ -1,36 +1,55 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
+use solution::{Answer, ParseError, Solution, Source};
 use std::{
     fmt::{Display, Write},
-    fs::read_to_string,
+    ops::RangeInclusive,
     path::Path,
 };
 
+mod generate;
+
+pub use generate::generate;
+
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct Input {
     octopodes: [Octopus; 100],
 }
 
-pub const PART_1: usize = 1681;
-pub const PART_2: usize = 276;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day11.parse_file(Path::new(data_dir).join("day_11.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_11.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_11.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day11.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    contents.lines().enumerate().fold(
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    if contents.lines().count() != 10 {
+        let row = contents.lines().nth(10).unwrap_or_else(|| source.end());
+        return Err(source.error(row, "10 rows of octopodes"));
+    }
+
+    contents.lines().enumerate().try_fold(
         Input {
             octopodes: [Octopus::default(); 100],
         },
         |mut acc, (y, row)| {
-            row.char_indices().for_each(|(x, chr)| {
+            if row.chars().count() != 10 {
+                return Err(source.error(row, "10 octopodes per row"));
+            }
+
+            for (x, _) in row.char_indices() {
                 let idx = (y * 10) + x;
-                acc.octopodes[idx].value = (chr as u8 - b'0') as u8;
+                acc.octopodes[idx].value = source.digit(&row[x..])?;
 
                 let x = x as isize;
                 let y = y as isize;
@@ -53,32 +72,34 @@ fn parse_contents(contents: &str) -> Input {
                         }
                         *n = target as usize;
                     });
-            });
-            acc
+            }
+            Ok(acc)
         },
     )
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut input = input.to_owned();
-    (0..100).fold(0usize, |mut flash_count, _| {
-        input
-            .octopodes
-            .iter_mut()
-            .for_each(|octopus| octopus.value += 1);
-
-        for i in 0..input.octopodes.len() {
-            if input.octopodes[i].value > 9 {
-                flash_count += input.flash(i);
+    (0..100)
+        .fold(0usize, |mut flash_count, _| {
+            input
+                .octopodes
+                .iter_mut()
+                .for_each(|octopus| octopus.value += 1);
+
+            for i in 0..input.octopodes.len() {
+                if input.octopodes[i].value > 9 {
+                    flash_count += input.flash(i);
+                }
             }
-        }
-        flash_count
-    })
+            flash_count
+        })
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let mut input = input.to_owned();
-    let mut iteration = 0;
+    let mut iteration = 0usize;
     loop {
         iteration += 1;
 
@@ -100,11 +121,42 @@ pub fn part_2(input: &Input) -> usize {
             .count()
             == 0
         {
-            return iteration;
+            return iteration.into();
         }
     }
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Input;
+
+    const DAY: u8 = 11;
+    const INPUT_SIZE: usize = 10;
+    const INPUT_SIZES: RangeInclusive<usize> = 10..=10;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
+}
+
 impl Default for Input {
     fn default() -> Self {
         Self {
@@ -130,7 +182,7 @@ impl Default for Octopus {
 
 impl Input {
     fn flash(&mut self, idx: usize) -> usize {
-        let mut octopus = &mut self.octopodes[idx];
+        let octopus = &mut self.octopodes[idx];
         if octopus.value < 9 {
             if octopus.value > 0 {
                 octopus.value += 1;
@@ -163,32 +215,38 @@ impl Display for Input {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day11::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data.octopodes[0], Octopus::default())
-            })
+            assert_ne!(data.octopodes[0], Octopus::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data.octopodes[0], Octopus::default())
         }
     }
 
@@ -217,8 +275,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:6: expected 10 rows of octopodes, found end of input",
+                parse_contents(
+                    "5483143223
+27456"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -227,25 +298,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 1656,
+                expected: Answer::Integer(1656),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day11::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -258,25 +329,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 195,
+                expected: Answer::Integer(195),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day11::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
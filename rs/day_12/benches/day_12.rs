@@ -0,0 +1,34 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_12::{part_1, part_2, read_data, Day12};
+use solution::{Answers, Solution};
+
+const DATA_DIR: &str = "../../data";
+
+fn total(c: &mut Criterion) {
+    let expected = Answers::expected(DATA_DIR, Day12::DAY).unwrap();
+
+    c.bench_function("total", |b| {
+        b.iter(|| {
+            let data = read_data(DATA_DIR).unwrap();
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
+        })
+    });
+}
+
+fn read(c: &mut Criterion) {
+    c.bench_function("read_data", |b| b.iter(|| read_data(DATA_DIR).unwrap()));
+
+    #[cfg(feature = "embed-input")]
+    c.bench_function("embedded", |b| b.iter(|| day_12::parse_embedded().unwrap()));
+}
+
+fn parts(c: &mut Criterion) {
+    let data = read_data(DATA_DIR).unwrap();
+
+    c.bench_function("part_1", |b| b.iter(|| part_1(&data)));
+    c.bench_function("part_2", |b| b.iter(|| part_2(&data)));
+}
+
+criterion_group!(benches, total, read, parts);
+criterion_main!(benches);
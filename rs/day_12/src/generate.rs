@@ -0,0 +1,83 @@
+use solution::Rng;
+
+/// Generates a cave system of `size` caves besides `start` and `end`, about a
+/// third of them large. Large caves are never connected to each other, as that
+/// would allow infinitely many paths. Visited caves are tracked in a 16 bit
+/// mask, so sizes above 14 are rejected when parsed.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut caves = vec![("start".to_owned(), false)];
+    while caves.len() <= size {
+        let large = rng.ratio(1, 3);
+        let name = (0..2)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        if !caves
+            .iter()
+            .any(|(cave, _)| cave.eq_ignore_ascii_case(&name))
+        {
+            caves.push(match large {
+                true => (name.to_ascii_uppercase(), true),
+                false => (name, false),
+            });
+        }
+    }
+    caves.push(("end".to_owned(), false));
+
+    let end = caves.len() - 1;
+    let mut edges: Vec<(usize, usize)> = Vec::new();
+    let can_connect = |edges: &[(usize, usize)], lhs: usize, rhs: usize| {
+        let both_large = caves[lhs].1 && caves[rhs].1;
+        let connected = edges.contains(&(lhs, rhs)) || edges.contains(&(rhs, lhs));
+        lhs != rhs && !both_large && !connected
+    };
+
+    // Connect every cave to an earlier one, so every cave is reachable, and
+    // only connect start to end directly when there are no other caves.
+    for cave in 1..caves.len() {
+        let earlier = (0..cave)
+            .filter(|&other| can_connect(&edges, cave, other))
+            .filter(|&other| cave != end || other != 0 || end == 1)
+            .collect::<Vec<_>>();
+        edges.push((*rng.choose(&earlier), cave));
+    }
+    for _ in 0..size / 2 {
+        let (lhs, rhs) = (rng.below(end), 1 + rng.below(end));
+        if can_connect(&edges, lhs, rhs) && (lhs, rhs) != (0, end) {
+            edges.push((lhs, rhs));
+        }
+    }
+
+    rng.shuffle(&mut edges);
+    edges
+        .into_iter()
+        .map(|(lhs, rhs)| match rng.ratio(1, 2) {
+            true => format!("{}-{}\n", caves[lhs].0, caves[rhs].0),
+            false => format!("{}-{}\n", caves[rhs].0, caves[lhs].0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day12};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 10), generate(1, 10));
+        assert_ne!(generate(1, 10), generate(2, 10));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day12.parse(&generate(seed, Day12::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day12::INPUT_SIZE + 2, input.nodes.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
@@ -1,39 +1,59 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{cmp::Ordering, fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{cmp::Ordering, path::Path};
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, PartialEq, PartialOrd, Default, Clone)]
 pub struct Input {
     nodes: Vec<Node>,
 }
 
-pub const PART_1: usize = 4912;
-pub const PART_2: usize = 150004;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day12.parse_file(Path::new(data_dir).join("day_12.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_12.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_12.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day12.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     let (mut nodes, edges) =
         contents
             .lines()
-            .fold((Vec::new(), Vec::new()), |(mut nodes, mut edges), line| {
-                let (lhs, rhs) = line.split_once('-').unwrap();
+            .try_fold((Vec::new(), Vec::new()), |(mut nodes, mut edges), line| {
+                let (lhs, rhs) = source.split_once(line, "-")?;
 
                 for node in [lhs, rhs] {
                     if !nodes.contains(&node.to_string()) {
+                        // paths are tracked as a bitmask of visited caves
+                        if nodes.len() == u16::BITS as usize {
+                            return Err(source.error(node, "at most 16 distinct caves"));
+                        }
                         nodes.push(node.to_owned());
                     }
                 }
                 edges.push((lhs.to_owned(), rhs.to_owned()));
 
-                (nodes, edges)
-            });
+                Ok((nodes, edges))
+            })?;
+
+    for required in ["start", "end"] {
+        if !nodes.iter().any(|n| n == required) {
+            return Err(source.error(source.end(), format!("a cave named '{}'", required)));
+        }
+    }
 
     nodes.sort_unstable_by(|a, b| {
         if a == "start" || b == "end" {
@@ -63,15 +83,45 @@ fn parse_contents(contents: &str) -> Input {
         .iter_mut()
         .for_each(|n| n.connections.sort_unstable());
 
-    Input { nodes: output }
+    Ok(Input { nodes: output })
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.paths_small_once(0, 1)
+pub fn part_1(input: &Input) -> Answer {
+    input.paths_small_once(0, 1).into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    input.paths_single_small_twice(0, 1, false)
+pub fn part_2(input: &Input) -> Answer {
+    input.paths_single_small_twice(0, 1, false).into()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Input;
+
+    const DAY: u8 = 12;
+    const INPUT_SIZE: usize = 11;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Default, Clone)]
@@ -119,32 +169,38 @@ impl Input {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day12::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -212,8 +268,21 @@ start-RW",
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:4: expected a cave named 'end', found end of input",
+                parse_contents(
+                    "start-A
+A-b"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -222,14 +291,14 @@ start-RW",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example_1() {
             run(&Case {
                 data: example_1_data(),
-                expected: 10,
+                expected: Answer::Integer(10),
             })
         }
 
@@ -237,7 +306,7 @@ start-RW",
         fn example_2() {
             run(&Case {
                 data: example_2_data(),
-                expected: 19,
+                expected: Answer::Integer(19),
             })
         }
 
@@ -245,18 +314,18 @@ start-RW",
         fn example_3() {
             run(&Case {
                 data: example_3_data(),
-                expected: 226,
+                expected: Answer::Integer(226),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day12::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -269,14 +338,14 @@ start-RW",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example_1() {
             run(&Case {
                 data: example_1_data(),
-                expected: 36,
+                expected: Answer::Integer(36),
             })
         }
 
@@ -284,7 +353,7 @@ start-RW",
         fn example_2() {
             run(&Case {
                 data: example_2_data(),
-                expected: 103,
+                expected: Answer::Integer(103),
             })
         }
 
@@ -292,18 +361,18 @@ start-RW",
         fn example_3() {
             run(&Case {
                 data: example_3_data(),
-                expected: 3509,
+                expected: Answer::Integer(3509),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day12::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
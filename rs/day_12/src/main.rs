@@ -1,8 +0,0 @@
-use day_12::*;
-
-fn main() {
-    let data = read_data("./data");
-
-    println!("Part 1: {}", part_1(&data));
-    println!("Part 2: {}", part_2(&data));
-}
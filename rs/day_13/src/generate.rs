@@ -0,0 +1,104 @@
+use super::LETTERS;
+use solution::Rng;
+use std::collections::BTreeSet;
+
+/// The number of folds in the real inputs, alternating between x and y.
+const FOLDS: usize = 12;
+
+/// Generates a sheet of at least `size` dots which, once folded, spells out
+/// eight random letters that part 2 can read.
+///
+/// The letters are drawn on a 40x6 sheet, which is then unfolded: before each
+/// fold, every dot was either where it ended up or at its reflection.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let letters = LETTERS[..26]
+        .iter()
+        .filter(|letter| !letter.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut pixels = Vec::new();
+    for idx in 0..8 {
+        let letter = rng.choose(&letters);
+        for (y, row) in letter.trim().lines().enumerate() {
+            for (x, chr) in row.char_indices() {
+                if chr == '#' {
+                    pixels.push(((5 * idx) + x, y));
+                }
+            }
+        }
+    }
+
+    // Folding along a line halves the sheet, so working backwards from the
+    // 40x6 sheet each fold line lies just beyond the smaller sheet. The last
+    // fold generated is the first made, which is along x like the real ones.
+    let (mut width, mut height) = (40, 6);
+    let mut folds = Vec::with_capacity(FOLDS);
+    for idx in 0..FOLDS {
+        if idx % 2 == 1 {
+            folds.push((width, 0));
+            width = (2 * width) + 1;
+        } else {
+            folds.push((0, height));
+            height = (2 * height) + 1;
+        }
+    }
+
+    let mut unfold = |(mut x, mut y): (usize, usize)| {
+        for &(fold_x, fold_y) in &folds {
+            if rng.ratio(1, 2) {
+                if fold_x > 0 {
+                    x = (2 * fold_x) - x;
+                } else {
+                    y = (2 * fold_y) - y;
+                }
+            }
+        }
+        (x, y)
+    };
+
+    let mut dots = pixels.iter().map(|&p| unfold(p)).collect::<BTreeSet<_>>();
+    let mut extra = pixels.iter().cycle();
+    while dots.len() < size {
+        dots.insert(unfold(*extra.next().unwrap()));
+    }
+    let mut dots = dots.into_iter().collect::<Vec<_>>();
+    rng.shuffle(&mut dots);
+
+    let mut output = dots
+        .iter()
+        .map(|(x, y)| format!("{},{}\n", x, y))
+        .collect::<String>();
+    output.push('\n');
+    for (x, y) in folds.into_iter().rev() {
+        match x {
+            0 => output.push_str(&format!("fold along y={}\n", y)),
+            _ => output.push_str(&format!("fold along x={}\n", x)),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day13};
+    use solution::{Answer, Solution};
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day13.parse(&generate(seed, Day13::INPUT_SIZE)).unwrap();
+
+            assert!(input.points.len() >= Day13::INPUT_SIZE);
+            part_1(&input);
+            assert!(matches!(part_2(&input), Answer::String(code) if code.len() == 8));
+        }
+    }
+}
@@ -1,8 +1,11 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{cmp::Ordering, fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{cmp::Ordering, path::Path};
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, PartialEq, PartialOrd, Default, Clone)]
 pub struct Input {
@@ -10,25 +13,34 @@ pub struct Input {
     folds: Vec<(usize, usize)>,
 }
 
-pub const PART_1: usize = 669;
-pub const PART_2: &str = "uefzcucj";
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day13.parse_file(Path::new(data_dir).join("day_13.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_13.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_13.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day13.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    let (point_strs, fold_strs) = contents.split_once("\n\n").unwrap();
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    let (point_strs, fold_strs) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| source.error(source.end(), "a blank line followed by fold instructions"))?;
 
     let mut points: Vec<(usize, usize)> = point_strs
         .lines()
         .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
+            let (x, y) = source.split_once(line, ",")?;
+            Ok((source.parse::<usize>(x)?, source.parse::<usize>(y)?))
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
     points.sort_unstable_by(|a, b| {
         let y_diff = a.1.cmp(&b.1);
         if y_diff != Ordering::Equal {
@@ -41,22 +53,20 @@ fn parse_contents(contents: &str) -> Input {
     let folds = fold_strs
         .lines()
         .map(|line| {
-            let (axis, coordinate) = line
-                .trim_start_matches("fold along ")
-                .split_once('=')
-                .unwrap();
+            let (axis, coordinate) =
+                source.split_once(line.trim_start_matches("fold along "), "=")?;
             match axis {
-                "x" => (coordinate.parse::<usize>().unwrap(), 0),
-                "y" => (0, coordinate.parse::<usize>().unwrap()),
-                _ => panic!("unknown coordinate: {}", axis),
+                "x" => Ok((source.parse::<usize>(coordinate)?, 0)),
+                "y" => Ok((0, source.parse::<usize>(coordinate)?)),
+                _ => Err(source.error(axis, "an axis of 'x' or 'y'")),
             }
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Input { points, folds }
+    Ok(Input { points, folds })
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let fold = input.folds.first().unwrap();
 
     let mut points = input.points.clone();
@@ -74,10 +84,10 @@ pub fn part_1(input: &Input) -> usize {
 
     points.sort_unstable();
     points.dedup();
-    points.len()
+    points.len().into()
 }
 
-pub fn part_2(input: &Input) -> String {
+pub fn part_2(input: &Input) -> Answer {
     let mut points = input.points.clone();
     for fold in &input.folds {
         points
@@ -99,7 +109,7 @@ pub fn part_2(input: &Input) -> String {
         (acc.0.max(x), acc.1.max(y))
     });
 
-    let num_chars = (max_x + 4) / 5;
+    let num_chars = max_x.div_ceil(5);
 
     let display: Vec<Vec<char>> = points.iter().fold(
         vec![vec!['.'; 5 * num_chars]; max_y + 1],
@@ -113,7 +123,6 @@ pub fn part_2(input: &Input) -> String {
         vec![String::with_capacity((max_y + 1) * 5); num_chars],
         |mut acc, line| {
             line.chunks(5).enumerate().for_each(|(char_idx, chunk)| {
-                dbg!(chunk.len());
                 for &char in chunk {
                     acc[char_idx].push(char);
                 }
@@ -135,6 +144,37 @@ pub fn part_2(input: &Input) -> String {
             acc.push(chr as char);
             acc
         })
+        .into()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Input;
+
+    const DAY: u8 = 13;
+    const INPUT_SIZE: usize = 800;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 // missing letters obtained from https://gist.github.com/Aidiakapi/3d8e32c3e552ca1258494cc114d8acf5
@@ -325,32 +365,38 @@ const LETTERS: [&str; 27] = [
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day13::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -390,8 +436,23 @@ fold along x=5",
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "4:12: expected an axis of 'x' or 'y', found \"z\"",
+                parse_contents(
+                    "6,10
+0,14
+
+fold along z=7"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -400,25 +461,25 @@ fold along x=5",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 17,
+                expected: Answer::Integer(17),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day13::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -429,27 +490,27 @@ fold along x=5",
     mod part_2 {
         use super::*;
 
-        struct Case<'c> {
+        struct Case {
             data: Input,
-            expected: &'c str,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: "{",
+                expected: Answer::from("{"),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day13::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,45 @@
+use solution::Rng;
+
+/// Generates a polymer template of `size` elements, at least 2, along with an
+/// insertion rule for every pair of its 10 distinct elements.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut elements = (b'A'..=b'Z').map(char::from).collect::<Vec<_>>();
+    rng.shuffle(&mut elements);
+    elements.truncate(10);
+
+    let mut output = (0..size.max(2))
+        .map(|_| *rng.choose(&elements))
+        .collect::<String>();
+    output.push_str("\n\n");
+    for &lhs in &elements {
+        for &rhs in &elements {
+            output.push_str(&format!("{}{} -> {}\n", lhs, rhs, rng.choose(&elements)));
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day14};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day14.parse(&generate(seed, Day14::INPUT_SIZE)).unwrap();
+
+            assert_eq!(100, input.insertions.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
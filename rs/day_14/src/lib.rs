@@ -1,8 +1,11 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{collections::HashMap, fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{collections::HashMap, path::Path};
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct Input {
@@ -12,17 +15,33 @@ pub struct Input {
     insertions: HashMap<u16, u16>,
 }
 
-pub const PART_1: u64 = 2584;
-pub const PART_2: u64 = 3816397135460;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day14.parse_file(Path::new(data_dir).join("day_14.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_14.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_14.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day14.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    let (input, rules) = contents.split_once("\n\n").unwrap();
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    let (input, rules) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| source.error(source.end(), "a blank line followed by insertion rules"))?;
+
+    if let Some(idx) = input.find(|c: char| !c.is_ascii_uppercase()) {
+        return Err(source.error(&input[idx..], "an uppercase element"));
+    }
+    if input.len() < 2 {
+        return Err(source.error(input, "a polymer template of at least 2 elements"));
+    }
 
     let initial: HashMap<u16, u64> =
         input
@@ -38,30 +57,68 @@ fn parse_contents(contents: &str) -> Input {
 
     let insertions = rules
         .lines()
-        .map(|line| line.split_once(" -> ").unwrap())
-        .fold(HashMap::new(), |mut acc, (pair, elem)| {
+        .map(|line| source.split_once(line, " -> "))
+        .try_fold(HashMap::new(), |mut acc, rule| {
+            let (pair, elem) = rule?;
+            if pair.len() != 2 || !pair.bytes().all(|b| b.is_ascii_uppercase()) {
+                return Err(source.error(pair, "a pair of uppercase elements"));
+            }
+            if elem.len() != 1 || !elem.bytes().all(|b| b.is_ascii_uppercase()) {
+                return Err(source.error(elem, "a single uppercase element"));
+            }
+
             let mut key_iter = pair.bytes();
             acc.insert(
                 (key_iter.next().unwrap() as u16) << 8 | key_iter.next().unwrap() as u16,
                 elem.as_bytes()[0] as u16,
             );
-            acc
-        });
+            Ok(acc)
+        })?;
 
-    Input {
+    Ok(Input {
         first: input.bytes().next().unwrap() - b'A',
         last: input.bytes().last().unwrap() - b'A',
         initial,
         insertions,
-    }
+    })
 }
 
-pub fn part_1(input: &Input) -> u64 {
-    input.polymerize(10)
+pub fn part_1(input: &Input) -> Answer {
+    input.polymerize(10).into()
 }
 
-pub fn part_2(input: &Input) -> u64 {
-    input.polymerize(40)
+pub fn part_2(input: &Input) -> Answer {
+    input.polymerize(40).into()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Input;
+
+    const DAY: u8 = 14;
+    const INPUT_SIZE: usize = 20;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 impl PartialOrd for Input {
@@ -137,32 +194,38 @@ impl Input {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day14::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -199,8 +262,23 @@ CN -> C",
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "4:1: expected \" -> \", found \"HH N\"",
+                parse_contents(
+                    "NNCB
+
+CH -> B
+HH N"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -209,25 +287,25 @@ CN -> C",
 
         struct Case {
             data: Input,
-            expected: u64,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 1588,
+                expected: Answer::Integer(1588),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day14::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -240,25 +318,25 @@ CN -> C",
 
         struct Case {
             data: Input,
-            expected: u64,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 2188189693529,
+                expected: Answer::Integer(2188189693529),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day14::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
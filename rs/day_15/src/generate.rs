@@ -0,0 +1,40 @@
+use solution::Rng;
+
+/// Generates a `size` by `size` map of risk levels from 1 to 9.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| (b'1' + rng.below(9) as u8) as char)
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day15};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day15.parse(&generate(seed, 20)).unwrap();
+
+            assert_eq!(20, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
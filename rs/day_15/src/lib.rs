@@ -1,38 +1,49 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Vec<usize>>;
 
-pub const PART_1: usize = 472;
-pub const PART_2: usize = 2851;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day15.parse_file(Path::new(data_dir).join("day_15.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_15.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_15.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day15.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
         .map(|l| {
-            l.bytes().fold(Vec::new(), |mut acc, b| {
-                acc.push((b - b'0') as usize);
-                acc
-            })
+            l.char_indices()
+                .map(|(idx, _)| source.digit(&l[idx..]).map(usize::from))
+                .collect()
         })
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let valid_x = 0..input[0].len();
     let valid_y = 0..input.len();
-    let end = ((input.len() - 1) << 9) | (input[0].len() - 1);
+    // Nodes are packed as `y << shift | x`.
+    let shift = usize::BITS - (input[0].len() - 1).leading_zeros();
+    let end = ((input.len() - 1) << shift) | (input[0].len() - 1);
 
-    let mut risks = vec![usize::MAX; 1 << 18];
+    let mut risks = vec![usize::MAX; input.len() << shift];
     risks[0] = 0;
 
     let mut to_process = Vec::with_capacity(risks.len());
@@ -40,10 +51,10 @@ pub fn part_1(input: &Input) -> usize {
     while let Some(node) = to_process.pop() {
         let risk = risks[node];
         if node == end {
-            return risk;
+            return risk.into();
         }
 
-        let (n_y, n_x) = (node >> 9, node & 0x1FF);
+        let (n_y, n_x) = (node >> shift, node & ((1 << shift) - 1));
 
         for (x, y) in [
             (n_x, n_y.wrapping_sub(1)),
@@ -54,7 +65,7 @@ pub fn part_1(input: &Input) -> usize {
             if !valid_x.contains(&x) || !valid_y.contains(&y) {
                 continue;
             }
-            let n = y << 9 | x;
+            let n = y << shift | x;
             let new_risk = risk + input[y][x];
             if new_risk < risks[n] {
                 risks[n] = new_risk;
@@ -68,7 +79,7 @@ pub fn part_1(input: &Input) -> usize {
     panic!("no path found")
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let (in_x, in_y) = (input.len(), input[0].len());
     let mut full_map = Vec::with_capacity(5 * in_y);
 
@@ -83,35 +94,71 @@ pub fn part_2(input: &Input) -> usize {
     part_1(&full_map)
 }
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Input;
+
+    const DAY: u8 = 15;
+    const INPUT_SIZE: usize = 100;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day15::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -140,8 +187,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:6: expected a digit, found \"a8511\"",
+                parse_contents(
+                    "1163751742
+13815a8511"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -150,25 +210,33 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 40,
+                expected: Answer::Integer(40),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn wide() {
+            run(&Case {
+                data: vec![vec![1; 600], vec![1; 600]],
+                expected: Answer::Integer(600),
+            })
+        }
+
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day15::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -181,25 +249,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 315,
+                expected: Answer::Integer(315),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day15::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
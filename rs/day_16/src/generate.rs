@@ -0,0 +1,127 @@
+use solution::Rng;
+
+/// Packets never evaluate above this, so no operation overflows a `usize`.
+const MAX_VALUE: u64 = 1 << 48;
+
+/// Generates a transmission of `size` packets, at least 1, nested at random.
+/// Operators are chosen so that every packet evaluates to at most 2^48.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let (mut bits, _) = packet(&mut rng, size.max(1));
+
+    while bits.len() % 8 != 0 {
+        bits.push('0');
+    }
+    let mut output = bits
+        .as_bytes()
+        .chunks(4)
+        .map(|nibble| {
+            let n = nibble
+                .iter()
+                .fold(0, |acc, &b| (acc << 1) | (b - b'0') as u32);
+            char::from_digit(n, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect::<String>();
+    output.push('\n');
+    output
+}
+
+/// Generates the bits of a packet containing `count` packets, including
+/// itself, and returns them along with the packet's value.
+fn packet(rng: &mut Rng, count: usize) -> (String, u64) {
+    let mut bits = binary(rng.below(8) as u64, 3);
+
+    if count == 1 {
+        let groups = rng.range(1..=4) as usize;
+        let value = rng.below(1 << (4 * groups)) as u64;
+        bits.push_str("100");
+        for group in (0..groups).rev() {
+            bits.push(if group > 0 { '1' } else { '0' });
+            bits.push_str(&binary(value >> (4 * group), 4));
+        }
+        return (bits, value);
+    }
+
+    // Split the packets left between 1 to 5 sub-packets.
+    let sub_packet_count = rng.range(1..=5).min(count as isize - 1) as usize;
+    let mut cuts = (0..sub_packet_count - 1)
+        .map(|_| 1 + rng.below(count - 2))
+        .collect::<Vec<_>>();
+    cuts.push(0);
+    cuts.push(count - 1);
+    cuts.sort_unstable();
+    cuts.dedup();
+    let sub_packets = cuts
+        .windows(2)
+        .map(|cut| packet(rng, cut[1] - cut[0]))
+        .collect::<Vec<_>>();
+    let values = sub_packets.iter().map(|&(_, value)| value);
+
+    let sum = values.clone().try_fold(0u64, |acc, v| acc.checked_add(v));
+    let product = values.clone().try_fold(1u64, |acc, v| acc.checked_mul(v));
+    let (type_id, value) = match rng.below(4) {
+        _ if sub_packets.len() == 2 && rng.ratio(1, 2) => {
+            let (lhs, rhs) = (sub_packets[0].1, sub_packets[1].1);
+            *rng.choose(&[
+                (5, (lhs > rhs) as u64),
+                (6, (lhs < rhs) as u64),
+                (7, (lhs == rhs) as u64),
+            ])
+        }
+        0 if sum.filter(|&v| v <= MAX_VALUE).is_some() => (0, sum.unwrap()),
+        1 if product.filter(|&v| v <= MAX_VALUE).is_some() => (1, product.unwrap()),
+        2 => (2, values.clone().min().unwrap()),
+        _ => (3, values.clone().max().unwrap()),
+    };
+    bits.push_str(&binary(type_id, 3));
+
+    let contents = sub_packets
+        .into_iter()
+        .map(|(bits, _)| bits)
+        .collect::<String>();
+    if contents.len() < 1 << 15 && rng.ratio(1, 2) {
+        bits.push('0');
+        bits.push_str(&binary(contents.len() as u64, 15));
+    } else {
+        bits.push('1');
+        bits.push_str(&binary((cuts.len() - 1) as u64, 11));
+    }
+    bits.push_str(&contents);
+
+    (bits, value)
+}
+
+fn binary(value: u64, width: usize) -> String {
+    format!("{:0width$b}", value & ((1 << width) - 1), width = width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day16, Packet};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let contents = generate(seed, Day16::INPUT_SIZE);
+            let input = Day16.parse(&contents).unwrap();
+
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+
+    #[test]
+    fn single_literal() {
+        let contents = generate(0, 1);
+
+        assert_ne!(Packet::Empty, Day16.parse(&contents).unwrap());
+    }
+}
@@ -1,30 +1,76 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Packet;
 
-pub const PART_1: usize = 877;
-pub const PART_2: usize = 194435634456;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day16.parse_file(Path::new(data_dir).join("day_16.txt"))
+}
+
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_16.txt");
+
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day16.parse(EMBEDDED_INPUT)
+}
+
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_16.txt")).unwrap();
+    if let Some(idx) = contents.find(|c: char| !c.is_ascii_hexdigit()) {
+        return Err(source.error(&contents[idx..], "a hexadecimal digit"));
+    }
 
-    parse_contents(contents.trim())
+    Biterator::new(contents)
+        .parse_packet()
+        .ok_or_else(|| source.error(source.end(), "the rest of the packet"))
 }
 
-fn parse_contents(contents: &str) -> Input {
-    Input::from(contents)
+pub fn part_1(input: &Input) -> Answer {
+    input.version_sum().into()
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.version_sum()
+pub fn part_2(input: &Input) -> Answer {
+    input.evaluate().into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    input.evaluate()
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Input;
+
+    const DAY: u8 = 16;
+    const INPUT_SIZE: usize = 268;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
@@ -38,19 +84,14 @@ enum Operation {
     EqualTo,
 }
 
-#[derive(Debug, PartialEq, PartialOrd, Clone)]
+#[derive(Debug, Default, PartialEq, PartialOrd, Clone)]
 pub enum Packet {
+    #[default]
     Empty,
     Operator(OperatorPacket),
     Literal(LiteralPacket),
 }
 
-impl Default for Packet {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 impl Packet {
     fn version_sum(&self) -> usize {
         match self {
@@ -258,32 +299,38 @@ impl Iterator for Biterator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day16::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Packet::Empty);
-            })
+            assert_ne!(data, Packet::Empty);
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Packet::Empty);
         }
     }
 
@@ -375,6 +422,14 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "1:9: expected a hexadecimal digit, found \"X\"",
+                parse_contents("8A004A80X").unwrap_err().to_string()
+            )
+        }
+
         fn run(test: &Case) {
             assert_eq!(test.expected, Input::from(test.input))
         }
@@ -385,14 +440,14 @@ mod tests {
 
         struct Case {
             data: Packet,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example_1() {
             run(&Case {
                 data: Input::from("8A004A801A8002F478"),
-                expected: 16,
+                expected: Answer::Integer(16),
             })
         }
 
@@ -400,7 +455,7 @@ mod tests {
         fn example_2() {
             run(&Case {
                 data: Input::from("620080001611562C8802118E34"),
-                expected: 12,
+                expected: Answer::Integer(12),
             })
         }
 
@@ -408,7 +463,7 @@ mod tests {
         fn example_3() {
             run(&Case {
                 data: Input::from("C0015000016115A2E0802F182340"),
-                expected: 23,
+                expected: Answer::Integer(23),
             })
         }
 
@@ -416,18 +471,18 @@ mod tests {
         fn example_4() {
             run(&Case {
                 data: Input::from("A0016C880162017C3686B18A3D4780"),
-                expected: 31,
+                expected: Answer::Integer(31),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day16::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -440,14 +495,14 @@ mod tests {
 
         struct Case {
             data: Packet,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn sum() {
             run(&Case {
                 data: Input::from("C200B40A82"),
-                expected: 3,
+                expected: Answer::Integer(3),
             });
         }
 
@@ -455,7 +510,7 @@ mod tests {
         fn product() {
             run(&Case {
                 data: Input::from("04005AC33890"),
-                expected: 54,
+                expected: Answer::Integer(54),
             });
         }
 
@@ -463,7 +518,7 @@ mod tests {
         fn minimum() {
             run(&Case {
                 data: Input::from("880086C3E88112"),
-                expected: 7,
+                expected: Answer::Integer(7),
             });
         }
 
@@ -471,7 +526,7 @@ mod tests {
         fn maximum() {
             run(&Case {
                 data: Input::from("CE00C43D881120"),
-                expected: 9,
+                expected: Answer::Integer(9),
             });
         }
 
@@ -479,7 +534,7 @@ mod tests {
         fn less_than() {
             run(&Case {
                 data: Input::from("D8005AC2A8F0"),
-                expected: 1,
+                expected: Answer::Integer(1),
             });
         }
 
@@ -487,7 +542,7 @@ mod tests {
         fn greater_than() {
             run(&Case {
                 data: Input::from("F600BC2D8F"),
-                expected: 0,
+                expected: Answer::Integer(0),
             });
         }
 
@@ -495,7 +550,7 @@ mod tests {
         fn equal_to() {
             run(&Case {
                 data: Input::from("9C005AC2F8F0"),
-                expected: 0,
+                expected: Answer::Integer(0),
             });
         }
 
@@ -503,18 +558,18 @@ mod tests {
         fn nested_equal_to() {
             run(&Case {
                 data: Input::from("9C0141080250320F1802104A08"),
-                expected: 1,
+                expected: Answer::Integer(1),
             });
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day16::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
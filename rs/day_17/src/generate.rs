@@ -0,0 +1,42 @@
+use solution::Rng;
+
+/// Generates a target area about `size` steps away, both right of and below
+/// the launcher, and roughly half that across.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(2) as isize;
+
+    let x_min = size + rng.range(0..=size / 2);
+    let x_max = x_min + rng.range(size / 4..=size / 2);
+    let y_max = -size - rng.range(0..=size / 2);
+    let y_min = y_max - rng.range(size / 4..=size / 2);
+
+    format!(
+        "target area: x={}..{}, y={}..{}\n",
+        x_min, x_max, y_min, y_max
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day17};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day17.parse(&generate(seed, Day17::INPUT_SIZE)).unwrap();
+
+            assert!(input[0][0] > 0 && input[1][1] < 0);
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
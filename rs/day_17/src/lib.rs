@@ -1,49 +1,59 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = [[isize; 2]; 2];
 
-pub const PART_1: usize = 12090;
-pub const PART_2: usize = 5059;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day17.parse_file(Path::new(data_dir).join("day_17.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_17.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_17.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day17.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    let (lhs, rhs) = contents.split_once(", ").unwrap();
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    let (lhs, rhs) = source.split_once(contents, ", ")?;
 
-    let (_, x_range) = lhs.split_once('=').unwrap();
-    let (_, y_range) = rhs.split_once('=').unwrap();
+    let (_, x_range) = source.split_once(lhs, "=")?;
+    let (_, y_range) = source.split_once(rhs, "=")?;
 
-    let (x_l_str, x_r_str) = x_range.split_once("..").unwrap();
-    let (y_l_str, y_r_str) = y_range.split_once("..").unwrap();
+    let (x_l_str, x_r_str) = source.split_once(x_range, "..")?;
+    let (y_l_str, y_r_str) = source.split_once(y_range, "..")?;
 
     let (x_l, x_r) = (
-        x_l_str.parse::<isize>().unwrap(),
-        x_r_str.parse::<isize>().unwrap(),
+        source.parse::<isize>(x_l_str)?,
+        source.parse::<isize>(x_r_str)?,
     );
 
     let (y_l, y_r) = (
-        y_l_str.parse::<isize>().unwrap(),
-        y_r_str.parse::<isize>().unwrap(),
+        source.parse::<isize>(y_l_str)?,
+        source.parse::<isize>(y_r_str)?,
     );
 
-    [[x_l, x_r], [y_l, y_r]]
+    Ok([[x_l, x_r], [y_l, y_r]])
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let best_y = find_max_y(input);
 
-    (best_y * (best_y + 1)) / 2
+    ((best_y * (best_y + 1)) / 2).into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     // min x when x=0 on target boundary
     // => x(x+1)/2 = min x
     let min_x = (((1.0 + (8.0 * input[0][0] as f64)).sqrt() - 1.0) / 2.0).ceil() as isize;
@@ -81,6 +91,37 @@ pub fn part_2(input: &Input) -> usize {
             }
         })
         .count()
+        .into()
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Input;
+
+    const DAY: u8 = 17;
+    const INPUT_SIZE: usize = 100;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 fn find_max_y(input: &Input) -> usize {
@@ -131,32 +172,38 @@ fn can_y_achieve_range(input: &Input, y: &isize) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day17::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -176,8 +223,18 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "1:31: expected isize, found \"five\"",
+                parse_contents("target area: x=20..30, y=-10..five")
+                    .unwrap_err()
+                    .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -186,25 +243,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 45,
+                expected: Answer::Integer(45),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day17::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -217,25 +274,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 112,
+                expected: Answer::Integer(112),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day17::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,171 @@
+use super::Point;
+use solution::Rng;
+
+/// How far a scanner can see along each axis.
+const RANGE: isize = 1000;
+
+/// How many places are tried for each beacon before giving up on the scanner
+/// it was for, as others may already see everywhere it could go.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// How many beacons each scanner sees, like the real inputs, unless it shares
+/// more than that with others.
+const BEACONS: usize = 26;
+
+/// How many beacons each scanner shares with the one it was placed next to.
+const SHARED: usize = 12;
+
+/// How many other scanners each can share beacons with, keeping how many it
+/// sees near [`BEACONS`].
+const MAX_LINKS: usize = 2;
+
+/// Generates the reports of `size` scanners, at least 1, each facing one of
+/// the 24 orientations. Every scanner after the first is placed within range
+/// of an earlier one, sharing [`SHARED`] beacons with it, so the scanners can
+/// all be pieced together.
+///
+/// Scanners are kept more than [`RANGE`] apart along some axis, and beacons
+/// are only placed where the scanners meant to see them can, so that like the
+/// real inputs each scanner sees around [`BEACONS`].
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut scanners = vec![Point::default()];
+    let mut links = vec![0];
+    let mut beacons = Vec::new();
+
+    while scanners.len() < size {
+        let open = (0..scanners.len())
+            .filter(|&idx| links[idx] < MAX_LINKS)
+            .collect::<Vec<_>>();
+        let neighbour = *rng.choose(&open);
+        let mut scanner = scanners[neighbour];
+        scanner += Point {
+            x: rng.range(-1200..=1200),
+            y: rng.range(-1200..=1200),
+            z: rng.range(-1200..=1200),
+        };
+        let crowded = scanners.iter().any(|&other| sees(other, scanner))
+            || beacons.iter().any(|&beacon| sees(scanner, beacon));
+        if crowded {
+            continue;
+        }
+
+        scanners.push(scanner);
+        let (idx, placed) = (scanners.len() - 1, beacons.len());
+        if scatter(&mut rng, &mut beacons, &scanners, neighbour, idx, SHARED) {
+            links[neighbour] += 1;
+            links.push(1);
+        } else {
+            scanners.pop();
+            beacons.truncate(placed);
+        }
+    }
+
+    for idx in 0..scanners.len() {
+        let seen = beacons.iter().filter(|&&b| sees(scanners[idx], b)).count();
+        // Any the scanner has no room for are left out, as it can already be
+        // pieced together with the others.
+        scatter(
+            &mut rng,
+            &mut beacons,
+            &scanners,
+            idx,
+            idx,
+            BEACONS.saturating_sub(seen),
+        );
+    }
+
+    let mut output = String::new();
+    for (idx, &scanner) in scanners.iter().enumerate() {
+        let facing = rng.below(24) as u8;
+        let mut seen = beacons
+            .iter()
+            .filter(|&&beacon| sees(scanner, beacon))
+            .map(|&beacon| beacon - scanner)
+            .collect::<Vec<_>>();
+        seen.iter_mut().for_each(|p| p.transform(facing));
+        rng.shuffle(&mut seen);
+
+        if idx > 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!("--- scanner {} ---\n", idx));
+        for p in seen {
+            output.push_str(&format!("{},{},{}\n", p.x, p.y, p.z));
+        }
+    }
+    output
+}
+
+/// Adds `count` beacons that the scanners `lhs` and `rhs` can both see, and
+/// none of the others can, returning whether there was room for them all.
+fn scatter(
+    rng: &mut Rng,
+    beacons: &mut Vec<Point>,
+    scanners: &[Point],
+    lhs: usize,
+    rhs: usize,
+    count: usize,
+) -> bool {
+    let (lhs, rhs) = (scanners[lhs], scanners[rhs]);
+    let mut axis = |lhs: isize, rhs: isize| rng.range(lhs.max(rhs) - RANGE..=lhs.min(rhs) + RANGE);
+    let mut added = 0;
+    for _ in 0..count * MAX_ATTEMPTS {
+        if added == count {
+            break;
+        }
+        let beacon = Point {
+            x: axis(lhs.x, rhs.x),
+            y: axis(lhs.y, rhs.y),
+            z: axis(lhs.z, rhs.z),
+        };
+        let hidden = scanners
+            .iter()
+            .all(|&other| other == lhs || other == rhs || !sees(other, beacon));
+        if hidden && !beacons.contains(&beacon) {
+            beacons.push(beacon);
+            added += 1;
+        }
+    }
+    added == count
+}
+
+/// Whether a scanner at `scanner` can see `point`.
+fn sees(scanner: Point, point: Point) -> bool {
+    let p = point - scanner;
+    p.x.abs() <= RANGE && p.y.abs() <= RANGE && p.z.abs() <= RANGE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day19};
+    use solution::{Answer, Solution};
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 10), generate(1, 10));
+        assert_ne!(generate(1, 10), generate(2, 10));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..3 {
+            let input = Day19.parse(&generate(seed, 5)).unwrap();
+
+            assert_ne!(Answer::Integer(0), part_1(&input));
+            part_2(&input);
+        }
+    }
+
+    #[test]
+    fn scale() {
+        let reports = generate(1, Day19::INPUT_SIZE);
+        let reports = reports.split("\n\n").collect::<Vec<_>>();
+
+        assert_eq!(Day19::INPUT_SIZE, reports.len());
+        for report in reports {
+            assert!((SHARED..=BEACONS).contains(&(report.lines().count() - 1)));
+        }
+    }
+}
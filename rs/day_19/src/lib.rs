@@ -1,11 +1,8 @@
 #![deny(clippy::all)]
-#![feature(test)]
-#![feature(drain_filter)]
-extern crate test;
 
+use solution::{Answer, ParseError, Solution, Source};
 use std::{
     cmp::Ordering,
-    fs::read_to_string,
     hash::Hash,
     ops::{AddAssign, Sub, SubAssign},
     path::Path,
@@ -13,44 +10,100 @@ use std::{
     thread,
 };
 
+mod generate;
+
+pub use generate::generate;
+
 #[derive(Debug, PartialEq, PartialOrd, Default, Clone)]
 pub struct Input {
     beacon_count: usize,
     max_scanner_distance: usize,
 }
 
-pub const PART_1: usize = 483;
-pub const PART_2: usize = 14804;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day19.parse_file(Path::new(data_dir).join("day_19.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_19.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_19.txt");
 
-    find_transforms(parse_contents(contents.trim()), 12)
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day19.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Vec<Vec<Point>> {
-    contents.split("\n\n").fold(Vec::new(), |mut acc, chunk| {
-        let scanner = chunk.split('\n').skip(1).fold(Vec::new(), |mut acc, line| {
-            let mut point_iter = line.split(',').map(|v| v.parse::<isize>().unwrap());
-            let point = Point {
-                x: point_iter.next().unwrap(),
-                y: point_iter.next().unwrap(),
-                z: point_iter.next().unwrap(),
-            };
-            acc.push(point);
-            acc
-        });
-        acc.push(scanner);
-        acc
-    })
+fn parse_contents(contents: &str) -> Result<Vec<Vec<Point>>, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    contents
+        .split("\n\n")
+        .try_fold(Vec::new(), |mut acc, chunk| {
+            let scanner = chunk
+                .split('\n')
+                .skip(1)
+                .try_fold(Vec::new(), |mut acc, line| {
+                    let mut coordinates = [0isize; 3];
+                    let mut values = line.split(',');
+                    for coordinate in coordinates.iter_mut() {
+                        let value = values.next().ok_or_else(|| {
+                            source.error(&line[line.len()..], "3 comma-separated coordinates")
+                        })?;
+                        *coordinate = source.parse::<isize>(value)?;
+                    }
+                    if let Some(extra) = values.next() {
+                        return Err(source.error(extra, "3 comma-separated coordinates"));
+                    }
+
+                    acc.push(Point {
+                        x: coordinates[0],
+                        y: coordinates[1],
+                        z: coordinates[2],
+                    });
+                    Ok(acc)
+                })?;
+            acc.push(scanner);
+            Ok(acc)
+        })
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    input.beacon_count.into()
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.beacon_count
+pub fn part_2(input: &Input) -> Answer {
+    input.max_scanner_distance.into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    input.max_scanner_distance
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = Input;
+
+    const DAY: u8 = 19;
+    const INPUT_SIZE: usize = 40;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        Ok(find_transforms(parse_contents(contents)?, 12))
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 fn find_transforms(input: Vec<Vec<Point>>, threshold: usize) -> Input {
@@ -100,10 +153,10 @@ fn find_transforms(input: Vec<Vec<Point>>, threshold: usize) -> Input {
         thread_pool.push(handle);
     }
 
-    let mut transforms = Vec::new();
-    while let Ok(t) = res_rx.recv_timeout(std::time::Duration::from_millis(200)) {
-        transforms.push(t);
-    }
+    // Only the workers' clones of the sender should keep the channel open, so
+    // that it closes once every pair has been tried.
+    drop(res_tx);
+    let mut transforms: Vec<Transform> = res_rx.iter().collect();
 
     for t in thread_pool {
         t.join().unwrap()
@@ -112,14 +165,12 @@ fn find_transforms(input: Vec<Vec<Point>>, threshold: usize) -> Input {
     let mut known_transforms = vec![0];
     while !transforms.is_empty() {
         let to_add: Vec<Transform> = {
-            let t: Vec<Transform> = transforms
-                .drain_filter(|t| known_transforms.contains(&t.lhs))
-                .collect();
+            let t = extract_matching(&mut transforms, |t| known_transforms.contains(&t.lhs));
             if !t.is_empty() {
                 t
             } else {
-                transforms
-                    .drain_filter(|t| known_transforms.contains(&t.rhs))
+                extract_matching(&mut transforms, |t| known_transforms.contains(&t.rhs))
+                    .into_iter()
                     .map(|mut t| {
                         t.rhs = t.lhs;
                         t
@@ -137,6 +188,17 @@ fn find_transforms(input: Vec<Vec<Point>>, threshold: usize) -> Input {
     fold_input(&input, known_transforms, threshold)
 }
 
+/// Removes the transforms matching `predicate` from `transforms` and returns
+/// them, keeping the order of both.
+fn extract_matching(
+    transforms: &mut Vec<Transform>,
+    predicate: impl Fn(&Transform) -> bool,
+) -> Vec<Transform> {
+    let (matching, rest) = transforms.iter().partition(|&t| predicate(t));
+    *transforms = rest;
+    matching
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 struct Transform {
     lhs: usize,
@@ -146,8 +208,15 @@ struct Transform {
 }
 
 fn find_translation(rhs: &[Point], lhs: &[Point], threshold: usize) -> Option<Point> {
-    for &lhs_glue in lhs.iter().take((lhs.len() - threshold).max(1)) {
-        for &rhs_glue in rhs.iter().take((rhs.len() - threshold).max(1)) {
+    // Any `threshold` beacons in common include one of the first
+    // `len - threshold + 1` of either side, but it could be anywhere in the
+    // other, so only the shorter side is cut short.
+    let (lhs_glues, rhs_glues) = match lhs.len() <= rhs.len() {
+        true => (lhs.len().saturating_sub(threshold) + 1, rhs.len()),
+        false => (lhs.len(), rhs.len().saturating_sub(threshold) + 1),
+    };
+    for &lhs_glue in lhs.iter().take(lhs_glues) {
+        for &rhs_glue in rhs.iter().take(rhs_glues) {
             let transform = rhs_glue - lhs_glue;
             let overlap = rhs
                 .iter()
@@ -289,19 +358,19 @@ impl Point {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day19::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
@@ -309,13 +378,20 @@ mod tests {
         use super::*;
 
         #[ignore = "takes 3s to run"]
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[ignore = "takes 3s to run"]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -354,8 +430,22 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:9: expected 3 comma-separated coordinates, found end of input",
+                parse_contents(
+                    "--- scanner 0 ---
+404,-588
+"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -364,26 +454,26 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 79,
+                expected: Answer::Integer(79),
             })
         }
 
         #[ignore = "takes 3s to run"]
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day19::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -396,26 +486,26 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 3621,
+                expected: Answer::Integer(3621),
             })
         }
 
         #[ignore = "takes 3s to run"]
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day19::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,49 @@
+use solution::Rng;
+
+/// Generates `size` submarine commands. Commands never take the submarine
+/// above the surface, as the solutions track depth and aim unsigned.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut aim = 0;
+
+    (0..size)
+        .map(|_| {
+            let distance = rng.range(1..=9);
+            let command = match rng.below(3) {
+                0 => "forward",
+                1 if aim >= distance => "up",
+                _ => "down",
+            };
+            match command {
+                "up" => aim -= distance,
+                "down" => aim += distance,
+                _ => {}
+            }
+            format!("{} {}\n", command, distance)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day2};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day2.parse(&generate(seed, Day2::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day2::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
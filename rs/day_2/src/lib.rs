@@ -1,28 +1,38 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path, str::FromStr};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{path::Path, str::FromStr};
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Instruction>;
 
-pub const PART_1: usize = 1840243;
-pub const PART_2: usize = 1727785422;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day2.parse_file(Path::new(data_dir).join("day_2.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_2.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_2.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day2.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
-        .map(|l| Instruction::from_str(l).unwrap())
+        .map(|l| Instruction::parse(&source, l))
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let (horizontal, depth) = input.iter().fold(
         (0usize, 0usize),
         |(mut horizontal, mut depth), instruction| {
@@ -34,10 +44,10 @@ pub fn part_1(input: &Input) -> usize {
             (horizontal, depth)
         },
     );
-    horizontal * depth
+    (horizontal * depth).into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let (horizontal, depth, _) = input.iter().fold(
         (0usize, 0usize, 0usize),
         |(mut horizontal, mut depth, mut aim), instruction| {
@@ -52,7 +62,37 @@ pub fn part_2(input: &Input) -> usize {
             (horizontal, depth, aim)
         },
     );
-    horizontal * depth
+    (horizontal * depth).into()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Input;
+
+    const DAY: u8 = 2;
+    const INPUT_SIZE: usize = 1000;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
@@ -62,51 +102,80 @@ pub enum Instruction {
     Up(usize),
 }
 
-impl FromStr for Instruction {
-    type Err = String;
+impl Instruction {
+    fn parse(source: &Source, line: &str) -> Result<Self, ParseError> {
+        let (instruction, distance_str) = source.split_once(line, " ")?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (instruction, distance_str) = s.split_once(' ').unwrap();
-
-        let distance = distance_str.parse::<usize>().unwrap();
+        let distance = source.parse::<usize>(distance_str)?;
         match instruction {
             "forward" => Ok(Instruction::Forward(distance)),
             "down" => Ok(Instruction::Down(distance)),
             "up" => Ok(Instruction::Up(distance)),
-            _ => Err(format!("unknown instruction: {}", instruction)),
+            _ => Err(source.error(instruction, "one of 'forward', 'down' or 'up'")),
         }
     }
 }
 
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Instruction::parse(&Source::new(s), s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day2::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::new())
-            })
+            assert_ne!(data, Input::new())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::new())
+        }
+    }
+
+    mod parse_contents {
+        use super::*;
+
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:1: expected one of 'forward', 'down' or 'up', found \"sideways\"",
+                parse_contents(
+                    "forward 5
+sideways 3"
+                )
+                .unwrap_err()
+                .to_string()
+            )
         }
     }
 
@@ -115,25 +184,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 150,
+                expected: Answer::Integer(150),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day2::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -146,25 +215,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 900,
+                expected: Answer::Integer(900),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day2::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
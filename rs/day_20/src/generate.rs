@@ -0,0 +1,47 @@
+use solution::Rng;
+
+/// Generates an enhancement algorithm and a `size` by `size` input image.
+///
+/// When the algorithm lights dark regions, it also darkens lit ones, like the
+/// real inputs, so the infinite image is finitely lit after every second step.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut pixel = || if rng.ratio(1, 2) { '#' } else { '.' };
+
+    let mut algorithm = (0..512).map(|_| pixel()).collect::<Vec<_>>();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+
+    let mut output = algorithm.into_iter().collect::<String>();
+    output.push('\n');
+    for _ in 0..size {
+        output.push('\n');
+        output.extend((0..size).map(|_| pixel()));
+    }
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day20};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day20.parse(&generate(seed, 10)).unwrap();
+
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
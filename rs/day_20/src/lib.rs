@@ -1,81 +1,112 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{cmp::Ordering, fs::read_to_string, hash::Hash, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = ImageProcessor;
 
-pub const PART_1: usize = 5359;
-pub const PART_2: usize = 12333;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day20.parse_file(Path::new(data_dir).join("day_20.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_20.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_20.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day20.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    let (algo, img) = contents.split_once("\n\n").unwrap();
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    let (algo, img) = contents
+        .split_once("\n\n")
+        .ok_or_else(|| source.error(source.end(), "a blank line followed by the input image"))?;
 
+    let pixel = |line: &str, idx: usize, chr: char| match chr {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(source.error(&line[idx..], "'#' or '.'")),
+    };
+
+    if algo.chars().count() != 512 {
+        return Err(source.error(algo, "an enhancement algorithm of 512 pixels"));
+    }
     let mut algorithm = [false; 512];
     for (idx, chr) in algo.char_indices() {
-        algorithm[idx] = chr == '#';
+        algorithm[idx] = pixel(algo, idx, chr)?;
     }
 
-    let image = img.lines().fold(Vec::new(), |mut acc, line| {
-        acc.push(line.chars().fold(Vec::new(), |mut acc, chr| {
-            acc.push(chr == '#');
-            acc
-        }));
-        acc
-    });
+    let image = img
+        .lines()
+        .map(|line| {
+            line.char_indices()
+                .map(|(idx, chr)| pixel(line, idx, chr))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
 
-    Input {
+    Ok(Input {
         algorithm,
         image,
         default: false,
-    }
+    })
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut image = input.clone();
 
     for _ in 0..2 {
         image.enhance();
     }
 
-    image.pixel_count()
+    image.pixel_count().into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let mut image = input.clone();
 
     for _ in 0..50 {
         image.enhance();
     }
 
-    image.pixel_count()
+    image.pixel_count().into()
 }
 
-#[derive(Debug, Eq, PartialOrd, Ord, Clone, Copy, Default)]
-struct Pixel {
-    x: isize,
-    y: isize,
-}
+pub struct Day20;
 
-impl Hash for Pixel {
-    fn hash<H>(&self, h: &mut H)
-    where
-        H: std::hash::Hasher,
-    {
-        h.write_u128((self.x as u128) << 64 | self.y as u128)
+impl Solution for Day20 {
+    type Input = Input;
+
+    const DAY: u8 = 20;
+    const INPUT_SIZE: usize = 100;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
     }
-}
 
-impl PartialEq for Pixel {
-    fn eq(&self, rhs: &Self) -> bool {
-        self.x.cmp(&rhs.x).then(self.y.cmp(&rhs.y)) == Ordering::Equal
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
     }
 }
 
@@ -155,32 +186,38 @@ impl ImageProcessor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day20::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -206,8 +243,22 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "1:1: expected an enhancement algorithm of 512 pixels, found \"#.#\"",
+                parse_contents(
+                    "#.#
+
+#..#."
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -216,25 +267,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 35,
+                expected: Answer::Integer(35),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day20::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -247,25 +298,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 3351,
+                expected: Answer::Integer(3351),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day20::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,36 @@
+use solution::Rng;
+
+/// Generates the starting positions of both players. There are always two
+/// players, so `size` is ignored.
+pub fn generate(seed: u64, _size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+        rng.range(1..=10),
+        rng.range(1..=10)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day21};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 2), generate(1, 2));
+        assert!((2..10).any(|seed| generate(1, 2) != generate(seed, 2)));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..3 {
+            let input = Day21.parse(&generate(seed, Day21::INPUT_SIZE)).unwrap();
+
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
@@ -1,30 +1,45 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = [u16; 2];
 
-pub const PART_1: usize = 802452;
-pub const PART_2: usize = 270005289024391;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day21.parse_file(Path::new(data_dir).join("day_21.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_21.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_21.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day21.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    let (l_1, l_2) = contents.split_once('\n').unwrap();
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
 
-    [
-        l_1.rsplit(' ').next().unwrap().parse::<u16>().unwrap(),
-        l_2.rsplit(' ').next().unwrap().parse::<u16>().unwrap(),
-    ]
+    let (l_1, l_2) = source.split_once(contents, "\n")?;
+
+    let position = |line: &str| {
+        let value = line.rsplit(' ').next().unwrap_or(line);
+        match source.parse::<u16>(value)? {
+            p @ 1..=10 => Ok(p),
+            _ => Err(source.error(value, "a starting position from 1 to 10")),
+        }
+    };
+
+    Ok([position(l_1)?, position(l_2)?])
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut die = (1..=100).cycle();
 
     let mut positions = input.to_owned();
@@ -41,19 +56,50 @@ pub fn part_1(input: &Input) -> usize {
             positions[player] = new_position;
             scores[player] += new_position;
             if scores[player] >= 1000 {
-                return 3 * (rolls + player) * scores[1 - player] as usize;
+                return (3 * (rolls + player) * scores[1 - player] as usize).into();
             }
         }
     }
-    0
+    Answer::Integer(0)
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     recursive_die_p0(input.to_owned(), [0u16; 2], 1)
         .iter()
         .max()
         .unwrap()
         .to_owned()
+        .into()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Input;
+
+    const DAY: u8 = 21;
+    const INPUT_SIZE: usize = 2;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 const PROBABILITIES: [(u16, usize); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
@@ -105,32 +151,38 @@ fn recursive_die_p1(positions: [u16; 2], scores: [u16; 2], probability: usize) -
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day21::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -151,8 +203,21 @@ Player 2 starting position: 8",
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:29: expected a starting position from 1 to 10, found \"11\"",
+                parse_contents(
+                    "Player 1 starting position: 4
+Player 2 starting position: 11"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -161,25 +226,25 @@ Player 2 starting position: 8",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 739785,
+                expected: Answer::Integer(739785),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day21::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -192,25 +257,25 @@ Player 2 starting position: 8",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 444356092776315,
+                expected: Answer::Integer(444356092776315),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day21::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,70 @@
+use solution::Rng;
+
+/// Generates `size` reboot steps. Like the real inputs, about one step in 21
+/// lies within the initialization region of -50..=50 on each axis, and these
+/// come first; the rest are cuboids thousands of cubes across.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let initialization_steps = size.div_ceil(21);
+
+    (0..size)
+        .map(|idx| {
+            let (start, extent, limit) = match idx < initialization_steps {
+                true => (-50..=20, 5..=50, 50),
+                false => (-99000..=70000, 5000..=30000, 100000),
+            };
+            let mut range = || {
+                let min = rng.range(start.clone());
+                (min, (min + rng.range(extent.clone())).min(limit))
+            };
+            let (x, y, z) = (range(), range(), range());
+            let state = if idx == 0 || rng.ratio(3, 4) {
+                "on"
+            } else {
+                "off"
+            };
+
+            format!(
+                "{} x={}..{},y={}..{},z={}..{}\n",
+                state, x.0, x.1, y.0, y.1, z.0, z.1
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day22};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day22.parse(&generate(seed, 21)).unwrap();
+
+            assert_eq!(21, input.instructions.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+
+    #[test]
+    fn initialization_region() {
+        let input = Day22.parse(&generate(1, 210)).unwrap();
+
+        for instruction in &input.instructions[..10] {
+            assert!(instruction
+                .bounds
+                .iter()
+                .flatten()
+                .all(|bound| (-50..=50).contains(bound)));
+        }
+    }
+}
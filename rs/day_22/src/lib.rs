@@ -1,9 +1,12 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
 use rayon::prelude::*;
-use std::{collections::HashSet, fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{collections::HashSet, path::Path};
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Input {
@@ -22,16 +25,22 @@ struct Instruction {
     bounds: [[isize; 2]; 3],
 }
 
-pub const PART_1: usize = 589411;
-pub const PART_2: usize = 1130514303649907;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day22.parse_file(Path::new(data_dir).join("day_22.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_22.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_22.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day22.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
     let mut instructions = Vec::with_capacity(contents.lines().count());
 
     let mut min_x = isize::MAX;
@@ -41,43 +50,50 @@ fn parse_contents(contents: &str) -> Input {
     let mut max_y = isize::MIN;
     let mut max_z = isize::MIN;
     for line in contents.lines() {
+        let (state, cuboid) = source.split_once(line, " ")?;
+        let on = match state {
+            "on" => true,
+            "off" => false,
+            _ => return Err(source.error(state, "'on' or 'off'")),
+        };
+
         let mut bounds = [[0isize; 2]; 3];
-        line.split_terminator('=')
-            .skip(1)
-            .enumerate()
-            .for_each(|(idx, l)| {
-                let (min, max) = l
-                    .trim_end_matches(&[',', 'x', 'y', 'z'])
-                    .split_once("..")
-                    .unwrap();
-                bounds[idx][0] = min.parse().unwrap();
-                bounds[idx][1] = max.parse().unwrap();
-                match idx {
-                    0 => {
-                        min_x = min_x.min(bounds[idx][0]);
-                        max_x = max_x.max(bounds[idx][1]);
-                    }
-                    1 => {
-                        min_y = min_y.min(bounds[idx][0]);
-                        max_y = max_y.max(bounds[idx][1]);
-                    }
-                    2 => {
-                        min_z = min_z.min(bounds[idx][0]);
-                        max_z = max_z.max(bounds[idx][1]);
-                    }
-                    _ => {}
+        let mut ranges = cuboid.split(',');
+        for (idx, axis) in ["x", "y", "z"].into_iter().enumerate() {
+            let range = ranges
+                .next()
+                .ok_or_else(|| source.error(&cuboid[cuboid.len()..], "x, y and z ranges"))?;
+            let (name, range) = source.split_once(range, "=")?;
+            if name != axis {
+                return Err(source.error(name, format!("'{}'", axis)));
+            }
+            let (min, max) = source.split_once(range, "..")?;
+            bounds[idx][0] = source.parse(min)?;
+            bounds[idx][1] = source.parse(max)?;
+            match idx {
+                0 => {
+                    min_x = min_x.min(bounds[idx][0]);
+                    max_x = max_x.max(bounds[idx][1]);
+                }
+                1 => {
+                    min_y = min_y.min(bounds[idx][0]);
+                    max_y = max_y.max(bounds[idx][1]);
+                }
+                2 => {
+                    min_z = min_z.min(bounds[idx][0]);
+                    max_z = max_z.max(bounds[idx][1]);
                 }
-            });
-        match line.chars().nth(1).unwrap() {
-            'n' => instructions.push(Instruction { on: true, bounds }),
-            'f' => instructions.push(Instruction { on: false, bounds }),
-            _ => {
-                panic!("invalid instruction: {}", line)
+                _ => {}
             }
-        };
+        }
+        if let Some(extra) = ranges.next() {
+            return Err(source.error(extra, "only x, y and z ranges"));
+        }
+
+        instructions.push(Instruction { on, bounds });
     }
 
-    Input {
+    Ok(Input {
         instructions,
         min_x,
         max_x,
@@ -85,10 +101,10 @@ fn parse_contents(contents: &str) -> Input {
         max_y,
         min_z,
         max_z,
-    }
+    })
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut cube = [[[false; 101]; 101]; 101];
     for instruction in &input.instructions {
         if instruction.bounds[0][0] < -50
@@ -109,10 +125,15 @@ pub fn part_1(input: &Input) -> usize {
             }
         }
     }
-    cube.iter().flatten().flatten().filter(|v| **v).count()
+    cube.iter()
+        .flatten()
+        .flatten()
+        .filter(|v| **v)
+        .count()
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let mut x_bound_set = HashSet::new();
     x_bound_set.insert((input.min_x * 2) - 1);
     x_bound_set.insert((input.max_x * 2) + 1);
@@ -181,38 +202,75 @@ pub fn part_2(input: &Input) -> usize {
             }
             total
         })
-        .sum()
+        .sum::<usize>()
+        .into()
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Input;
+
+    const DAY: u8 = 22;
+    const INPUT_SIZE: usize = 420;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day22::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -331,8 +389,21 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:1: expected 'on' or 'off', found \"toggle\"",
+                parse_contents(
+                    "on x=10..12,y=10..12,z=10..12
+toggle x=11..13,y=11..13,z=11..13"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -341,14 +412,14 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn small_example() {
             run(&Case {
                 data: small_example_data(),
-                expected: 39,
+                expected: Answer::Integer(39),
             })
         }
 
@@ -356,7 +427,7 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
         fn large_example() {
             run(&Case {
                 data: large_example_data(),
-                expected: 590784,
+                expected: Answer::Integer(590784),
             })
         }
 
@@ -364,18 +435,18 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
         fn extra_large_example() {
             run(&Case {
                 data: extra_large_example_data(),
-                expected: 474140,
+                expected: Answer::Integer(474140),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day22::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -388,14 +459,14 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn small_example() {
             run(&Case {
                 data: small_example_data(),
-                expected: 39,
+                expected: Answer::Integer(39),
             })
         }
 
@@ -403,13 +474,15 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
         fn large_example() {
             run(&Case {
                 data: large_example_data(),
-                expected: 590784
-                    + (((-54112isize).abs_diff(-39298) + 1)
-                        * ((-85059isize).abs_diff(-49293) + 1)
-                        * ((-27449isize).abs_diff(7877) + 1))
-                    + ((967isize.abs_diff(23432) + 1)
-                        * (45373isize.abs_diff(81175) + 1)
-                        * (27513isize.abs_diff(53682) + 1)),
+                expected: Answer::from(
+                    590784
+                        + (((-54112isize).abs_diff(-39298) + 1)
+                            * ((-85059isize).abs_diff(-49293) + 1)
+                            * ((-27449isize).abs_diff(7877) + 1))
+                        + ((967isize.abs_diff(23432) + 1)
+                            * (45373isize.abs_diff(81175) + 1)
+                            * (27513isize.abs_diff(53682) + 1)),
+                ),
             })
         }
 
@@ -417,18 +490,18 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507",
         fn extra_large_example() {
             run(&Case {
                 data: extra_large_example_data(),
-                expected: 2758514936282235,
+                expected: Answer::Integer(2758514936282235),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day22::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
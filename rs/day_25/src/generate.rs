@@ -0,0 +1,120 @@
+use solution::Rng;
+
+/// How many steps, per location across, a generated sea floor may take to
+/// stop moving. The real inputs take around three.
+const STEPS_PER_SIZE: usize = 10;
+
+/// How many random sea floors are drawn before falling back to one that is
+/// sure to stop moving.
+const MAX_GRIDS: usize = 10;
+
+/// Generates a `size` by `size` sea floor, with a quarter of the locations
+/// holding east-facing sea cucumbers, a quarter south-facing ones, and the
+/// rest empty, like the real inputs.
+///
+/// Some sea floors, small ones especially, never stop moving, so they are
+/// drawn until one stops within [`STEPS_PER_SIZE`] steps per location across.
+/// If none of [`MAX_GRIDS`] do, the top row is filled with east-facing sea
+/// cucumbers and the left column with south-facing ones. Neither can ever
+/// move, and no other sea cucumber can get past them, so every one stops.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut draw = || {
+        (0..size * size)
+            .map(|_| match rng.below(4) {
+                0 => b'>',
+                1 => b'v',
+                _ => b'.',
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let grid = (0..MAX_GRIDS)
+        .map(|_| draw())
+        .find(|grid| stops(grid.clone(), size))
+        .unwrap_or_else(|| {
+            let mut grid = draw();
+            for idx in 0..size {
+                grid[idx] = b'>';
+                grid[idx * size] = b'v';
+            }
+            grid
+        });
+
+    grid.chunks(size)
+        .map(|row| {
+            let mut row = row.iter().map(|&c| c as char).collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// Whether every sea cucumber stops within [`STEPS_PER_SIZE`] steps per
+/// location across.
+fn stops(mut grid: Vec<u8>, size: usize) -> bool {
+    for _ in 0..STEPS_PER_SIZE * size {
+        let east = step(&mut grid, size, b'>', |x, y| ((x + 1) % size, y));
+        let south = step(&mut grid, size, b'v', |x, y| (x, (y + 1) % size));
+        if !east && !south {
+            return true;
+        }
+    }
+    false
+}
+
+/// Moves every sea cucumber of `herd` whose next location is empty at once,
+/// returning whether any moved.
+fn step(
+    grid: &mut [u8],
+    size: usize,
+    herd: u8,
+    next: impl Fn(usize, usize) -> (usize, usize),
+) -> bool {
+    let next = |idx: usize| {
+        let (x, y) = next(idx % size, idx / size);
+        y * size + x
+    };
+    let moving = (0..grid.len())
+        .filter(|&idx| grid[idx] == herd && grid[next(idx)] == b'.')
+        .collect::<Vec<_>>();
+    for &idx in &moving {
+        grid[idx] = b'.';
+        grid[next(idx)] = herd;
+    }
+    !moving.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, Day25};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day25.parse(&generate(seed, 20)).unwrap();
+
+            assert_eq!(20, input.len());
+            part_1(&input);
+        }
+    }
+
+    #[test]
+    fn small() {
+        for size in 1..20 {
+            for seed in 0..10 {
+                let input = Day25.parse(&generate(seed, size)).unwrap();
+
+                part_1(&input);
+            }
+        }
+    }
+}
@@ -1,42 +1,55 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Vec<Option<bool>>>;
 
-pub const PART_1: usize = 453;
-pub const PART_2: usize = 0;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day25.parse_file(Path::new(data_dir).join("day_25.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_25.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_25.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day25.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
-    contents.lines().fold(Vec::new(), |mut rows, line| {
-        let row = line.chars().fold(Vec::new(), |mut row, chr| {
-            match chr {
-                '>' => row.push(Some(false)),
-                'v' => row.push(Some(true)),
-                _ => row.push(None),
-            }
-            row
-        });
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    contents.lines().try_fold(Vec::new(), |mut rows, line| {
+        let row = line
+            .char_indices()
+            .try_fold(Vec::new(), |mut row, (idx, chr)| {
+                match chr {
+                    '>' => row.push(Some(false)),
+                    'v' => row.push(Some(true)),
+                    '.' => row.push(None),
+                    _ => return Err(source.error(&line[idx..], "'>', 'v' or '.'")),
+                }
+                Ok(row)
+            })?;
         rows.push(row);
-        rows
+        Ok(rows)
     })
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut cucumbers = input.clone();
 
     let max_x = cucumbers[0].len() - 1;
     let max_y = cucumbers.len() - 1;
 
-    for step in 1.. {
+    for step in 1usize.. {
         let mut moved = false;
         let mut new_cucumbers = cucumbers.clone();
 
@@ -81,46 +94,82 @@ pub fn part_1(input: &Input) -> usize {
         }
 
         if !moved {
-            return step;
+            return step.into();
         }
         cucumbers = new_cucumbers;
     }
     unreachable!()
 }
 
-pub const fn part_2(_input: &Input) -> usize {
-    0
+pub const fn part_2(_input: &Input) -> Answer {
+    Answer::Empty
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Input;
+
+    const DAY: u8 = 25;
+    const INPUT_SIZE: usize = 137;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day25::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -148,8 +197,23 @@ v.v..>>v.v
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "4:3: expected '>', 'v' or '.', found \"x\"",
+                parse_contents(
+                    "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+..x"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -158,25 +222,25 @@ v.v..>>v.v
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 58,
+                expected: Answer::Integer(58),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day25::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -189,25 +253,25 @@ v.v..>>v.v
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 0,
+                expected: Answer::Empty,
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day25::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
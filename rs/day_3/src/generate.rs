@@ -0,0 +1,40 @@
+use solution::Rng;
+
+/// Generates a diagnostic report of `size` distinct 12-bit numbers, at most
+/// 4096 of them. Like the real inputs, no number repeats, so the ratings
+/// always filter down to a single number.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut numbers = (0..1 << 12).collect::<Vec<usize>>();
+    rng.shuffle(&mut numbers);
+
+    numbers
+        .into_iter()
+        .take(size)
+        .map(|number| format!("{:012b}\n", number))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day3};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day3.parse(&generate(seed, Day3::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day3::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
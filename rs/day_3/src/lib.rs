@@ -1,28 +1,46 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Vec<bool>>;
 
-pub const PART_1: usize = 3148794;
-pub const PART_2: usize = 2795310;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day3.parse_file(Path::new(data_dir).join("day_3.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_3.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_3.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day3.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
-        .map(|line| line.chars().map(|c| c.eq(&'1')).collect())
+        .map(|line| {
+            line.char_indices()
+                .map(|(idx, c)| match c {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    _ => Err(source.error(&line[idx..], "a binary digit")),
+                })
+                .collect()
+        })
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let entry_len = input[0].len();
     let (gamma, epsilon) = input
         .iter()
@@ -51,10 +69,10 @@ pub fn part_1(input: &Input) -> usize {
             },
         );
 
-    epsilon * gamma
+    (epsilon * gamma).into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let entry_len = input[0].len();
 
     let oxygen_generator_rating = {
@@ -100,13 +118,13 @@ pub fn part_2(input: &Input) -> usize {
             if filtered_input.len() == 1 {
                 break;
             }
-            let target = filtered_input.iter().fold(0usize, |mut acc, num| {
-                if num[idx] {
-                    acc += 1
-                }
-                acc
-            }) << 1
-                < filtered_input.len();
+            let ones = filtered_input.iter().filter(|num| num[idx]).count();
+            // When every remaining number shares this bit, keep them all.
+            let target = match ones {
+                0 => false,
+                ones if ones == filtered_input.len() => true,
+                ones => ones << 1 < filtered_input.len(),
+            };
 
             filtered_input = filtered_input
                 .iter()
@@ -131,38 +149,74 @@ pub fn part_2(input: &Input) -> usize {
             })
     };
 
-    oxygen_generator_rating * co2_scrubber_rating
+    (oxygen_generator_rating * co2_scrubber_rating).into()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Input;
+
+    const DAY: u8 = 3;
+    const INPUT_SIZE: usize = 1000;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day3::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::new())
-            })
+            assert_ne!(data, Input::new())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::new())
         }
     }
 
@@ -193,8 +247,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:4: expected a binary digit, found \"20\"",
+                parse_contents(
+                    "00100
+11020"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -203,25 +270,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 198,
+                expected: Answer::Integer(198),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day3::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -234,25 +301,33 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 230,
+                expected: Answer::Integer(230),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn shared_bit() {
+            run(&Case {
+                data: vec![vec![true, true], vec![true, false]],
+                expected: Answer::Integer(6),
+            })
+        }
+
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day3::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,52 @@
+use solution::Rng;
+
+/// Generates a bingo game with `size` boards. Every number from 0 to 99 is
+/// drawn, so every board eventually wins.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut numbers = (0..100).collect::<Vec<usize>>();
+
+    rng.shuffle(&mut numbers);
+    let mut output = numbers
+        .iter()
+        .map(usize::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    output.push('\n');
+
+    for _ in 0..size {
+        rng.shuffle(&mut numbers);
+        output.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row = row.iter().map(|n| format!("{:>2}", n)).collect::<Vec<_>>();
+            output.push_str(&row.join(" "));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day4};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day4.parse(&generate(seed, Day4::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day4::INPUT_SIZE, input.boards.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
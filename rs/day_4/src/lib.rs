@@ -1,8 +1,11 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Default)]
 pub struct Input {
@@ -10,31 +13,39 @@ pub struct Input {
     boards: Vec<Board>,
 }
 
-pub const PART_1: usize = 8580;
-pub const PART_2: usize = 9576;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day4.parse_file(Path::new(data_dir).join("day_4.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_4.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_4.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day4.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
     let mut day_parts = contents.split("\n\n");
 
     let numbers = day_parts
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .split(',')
-        .map(|v| v.parse::<usize>().unwrap())
-        .collect();
+        .map(|v| source.parse::<usize>(v))
+        .collect::<Result<_, _>>()?;
 
-    let boards = day_parts.map(board_from_str).collect();
+    let boards = day_parts
+        .map(|board| board_from_str(&source, board))
+        .collect::<Result<_, _>>()?;
 
-    Input { numbers, boards }
+    Ok(Input { numbers, boards })
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let mut boards = input.boards.clone();
     for num in &input.numbers {
         for board in boards.iter_mut() {
@@ -51,7 +62,7 @@ pub fn part_1(input: &Input) -> usize {
                 let col_has_won = board.iter().skip(i).step_by(5).all(|v| v.is_none());
 
                 if row_has_won || col_has_won {
-                    return board.iter().filter_map(|v| *v).sum::<usize>() * num;
+                    return (board.iter().filter_map(|v| *v).sum::<usize>() * num).into();
                 }
             }
         }
@@ -59,7 +70,7 @@ pub fn part_1(input: &Input) -> usize {
     panic!("no result")
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let mut boards = input.boards.clone();
     for num in &input.numbers {
         boards.iter_mut().for_each(|b| {
@@ -85,53 +96,95 @@ pub fn part_2(input: &Input) -> usize {
             .collect::<Vec<Board>>();
 
         if new_boards.is_empty() {
-            return boards[0].iter().filter_map(|v| *v).sum::<usize>() * num;
+            return (boards[0].iter().filter_map(|v| *v).sum::<usize>() * num).into();
         }
         boards = new_boards;
     }
     panic!("no result")
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Input;
+
+    const DAY: u8 = 4;
+    const INPUT_SIZE: usize = 100;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
+}
+
 pub type Board = [Option<usize>; 25];
 
-fn board_from_str(input: &str) -> Board {
+fn board_from_str(source: &Source, input: &str) -> Result<Board, ParseError> {
     let mut b = [None; 25];
-    input
-        .split_ascii_whitespace()
-        .enumerate()
-        .for_each(|(idx, v)| b[idx] = Some(v.parse::<usize>().unwrap()));
-    b
+    let mut values = input.split_ascii_whitespace();
+    for cell in b.iter_mut() {
+        let v = values
+            .next()
+            .ok_or_else(|| source.error(&input[input.len()..], "25 numbers per board"))?;
+        *cell = Some(source.parse::<usize>(v)?);
+    }
+    if let Some(extra) = values.next() {
+        return Err(source.error(extra, "25 numbers per board"));
+    }
+    Ok(b)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day4::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data.numbers, Vec::new())
-            })
+            assert_ne!(data.numbers, Vec::new())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data.numbers, Vec::new())
         }
     }
 
@@ -169,8 +222,22 @@ mod tests {
             });
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "3:14: expected 25 numbers per board, found end of input",
+                parse_contents(
+                    "7,4,9
+
+22 13 17 11 0"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -179,25 +246,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 4512,
+                expected: Answer::Integer(4512),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day4::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -210,25 +277,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 1924,
+                expected: Answer::Integer(1924),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day4::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,51 @@
+use solution::Rng;
+
+const SIDE: isize = 1000;
+
+/// Generates `size` horizontal, vertical and diagonal lines of vents on a
+/// 1000x1000 grid.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    (0..size)
+        .map(|_| {
+            let (x_1, y_1) = (rng.range(0..=SIDE - 1), rng.range(0..=SIDE - 1));
+            let (x_2, y_2) = match rng.below(3) {
+                0 => (rng.range(0..=SIDE - 1), y_1),
+                1 => (x_1, rng.range(0..=SIDE - 1)),
+                _ => {
+                    let (x_dir, y_dir) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                    let room =
+                        |from: isize, dir: isize| if dir > 0 { SIDE - 1 - from } else { from };
+                    let len = rng.range(0..=room(x_1, x_dir).min(room(y_1, y_dir)));
+                    (x_1 + x_dir * len, y_1 + y_dir * len)
+                }
+            };
+            format!("{},{} -> {},{}\n", x_1, y_1, x_2, y_2)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day5};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day5.parse(&generate(seed, Day5::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day5::INPUT_SIZE, input.vectors.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
@@ -1,8 +1,11 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 #[derive(Debug, PartialEq, PartialOrd, Default, Clone)]
 pub struct Input {
@@ -10,36 +13,35 @@ pub struct Input {
     max: (usize, usize),
 }
 
-pub const PART_1: usize = 5608;
-pub const PART_2: usize = 20299;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day5.parse_file(Path::new(data_dir).join("day_5.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_5.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_5.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day5.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
+    let parse_point = |point: &str| -> Result<(usize, usize), ParseError> {
+        let (x, y) = source.split_once(point, ",")?;
+        Ok((source.parse::<usize>(x)?, source.parse::<usize>(y)?))
+    };
+
     let vectors: Vec<[(usize, usize); 2]> = contents
         .lines()
         .map(|l| {
-            l.split_once(" -> ")
-                .map(|(from_str, to_str)| {
-                    let from = from_str
-                        .split_once(',')
-                        .map(|(x, y)| (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()))
-                        .unwrap();
-
-                    let to = to_str
-                        .split_once(',')
-                        .map(|(x, y)| (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap()))
-                        .unwrap();
-
-                    [from, to]
-                })
-                .unwrap()
+            let (from_str, to_str) = source.split_once(l, " -> ")?;
+            Ok([parse_point(from_str)?, parse_point(to_str)?])
         })
-        .collect();
+        .collect::<Result<_, ParseError>>()?;
 
     let max = vectors.iter().fold(
         (0, 0),
@@ -48,17 +50,17 @@ fn parse_contents(contents: &str) -> Input {
         },
     );
 
-    Input { vectors, max }
+    Ok(Input { vectors, max })
 }
 
-pub fn part_1(input: &Input) -> usize {
-    let row_width = input.max.0 - 1;
+pub fn part_1(input: &Input) -> Answer {
+    let row_width = input.max.1 + 1;
     input
         .vectors
         .iter()
         .filter(|[(from_x, from_y), (to_x, to_y)]| from_x.eq(to_x) || from_y.eq(to_y))
         .fold::<Vec<Option<usize>>, _>(
-            vec![None; input.max.0 * input.max.1],
+            vec![None; (input.max.0 + 1) * row_width],
             |mut counts, &[(from_x, from_y), (to_x, to_y)]| {
                 for x in from_x.min(to_x)..=from_x.max(to_x) {
                     for y in from_y.min(to_y)..=from_y.max(to_y) {
@@ -76,15 +78,16 @@ pub fn part_1(input: &Input) -> usize {
         .iter()
         .filter(|&&v| matches!(v, Some(n) if n > 1))
         .count()
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    let row_width = input.max.0 - 1;
+pub fn part_2(input: &Input) -> Answer {
+    let row_width = input.max.1 + 1;
     input
         .vectors
         .iter()
         .fold::<Vec<Option<usize>>, _>(
-            vec![None; input.max.0 * input.max.1],
+            vec![None; (input.max.0 + 1) * row_width],
             |mut counts, &[(from_x, from_y), (to_x, to_y)]| {
                 let (x_inc, y_inc) = (
                     -1 + (to_x >= from_x) as isize + (to_x > from_x) as isize,
@@ -121,37 +124,74 @@ pub fn part_2(input: &Input) -> usize {
         .iter()
         .filter(|&&v| matches!(v, Some(n) if n > 1))
         .count()
+        .into()
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Input;
+
+    const DAY: u8 = 5;
+    const INPUT_SIZE: usize = 500;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day5::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data.vectors, Vec::<[(usize, usize); 2]>::new())
-            })
+            assert_ne!(data.vectors, Vec::<[(usize, usize); 2]>::new())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data.vectors, Vec::<[(usize, usize); 2]>::new())
         }
     }
 
@@ -180,8 +220,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:1: expected \" -> \", found \"8,0 => 0,8\"",
+                parse_contents(
+                    "0,9 -> 5,9
+8,0 => 0,8"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -190,25 +243,34 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 5,
+                expected: Answer::Integer(5),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn distinct_points() {
+            // Points that only collide if the grid's row width is wrong.
+            run(&Case {
+                data: parse_contents("0,3 -> 0,3\n1,1 -> 1,1\n3,0 -> 3,0").unwrap(),
+                expected: Answer::Integer(0),
+            })
+        }
+
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day5::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -221,25 +283,34 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 12,
+                expected: Answer::Integer(12),
+            })
+        }
+
+        #[test]
+        fn distinct_points() {
+            // Points that only collide if the grid's row width is wrong.
+            run(&Case {
+                data: parse_contents("0,3 -> 0,3\n1,1 -> 1,1\n3,0 -> 3,0").unwrap(),
+                expected: Answer::Integer(0),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day5::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,35 @@
+use solution::Rng;
+
+/// Generates the timers of `size` lanternfish.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let timers = (0..size)
+        .map(|_| rng.range(1..=5).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n", timers.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day6};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day6.parse(&generate(seed, Day6::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day6::INPUT_SIZE, input.iter().sum::<usize>());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
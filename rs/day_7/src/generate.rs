@@ -0,0 +1,36 @@
+use solution::Rng;
+
+/// Generates the positions of `size` crabs, spread over twice as many
+/// positions as there are crabs.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    let positions = (0..size)
+        .map(|_| rng.below(2 * size).to_string())
+        .collect::<Vec<_>>();
+    format!("{}\n", positions.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day7};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day7.parse(&generate(seed, Day7::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day7::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
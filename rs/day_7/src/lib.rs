@@ -1,74 +1,137 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<usize>;
 
-pub const PART_1: usize = 340056;
-pub const PART_2: usize = 96592275;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day7.parse_file(Path::new(data_dir).join("day_7.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_7.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_7.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day7.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     let mut crabs: Input = contents
         .split(',')
-        .map(|num| num.parse::<usize>().unwrap())
-        .collect();
+        .map(|num| source.parse::<usize>(num))
+        .collect::<Result<_, _>>()?;
     crabs.sort_unstable();
-    crabs
+    Ok(crabs)
 }
 
-pub fn part_1(input: &Input) -> usize {
+pub fn part_1(input: &Input) -> Answer {
     let target = input[input.len() / 2];
-    input.iter().fold(0usize, |acc, &ship| {
-        acc + (ship as isize - target as isize).abs() as usize
-    })
+    input
+        .iter()
+        .fold(0usize, |acc, &ship| {
+            acc + (ship as isize - target as isize).unsigned_abs()
+        })
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    (0..*input.last().unwrap()).fold(usize::MAX, |total, target| {
-        total.min(input.iter().fold(0usize, |subtotal, &crab| {
-            let diff = (crab as isize - target as isize).abs() as usize;
-            subtotal + ((diff * (diff + 1)) / 2)
-        }))
-    })
+pub fn part_2(input: &Input) -> Answer {
+    (0..*input.last().unwrap())
+        .fold(usize::MAX, |total, target| {
+            total.min(input.iter().fold(0usize, |subtotal, &crab| {
+                let diff = (crab as isize - target as isize).unsigned_abs();
+                subtotal + ((diff * (diff + 1)) / 2)
+            }))
+        })
+        .into()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Input;
+
+    const DAY: u8 = 7;
+    const INPUT_SIZE: usize = 1000;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day7::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
+        }
+    }
+
+    mod parse_contents {
+        use super::*;
+
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "1:6: expected usize, found \"a\"",
+                parse_contents("16,1,a").unwrap_err().to_string()
+            )
         }
     }
 
@@ -77,25 +140,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 37,
+                expected: Answer::Integer(37),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day7::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -108,25 +171,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 168,
+                expected: Answer::Integer(168),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day7::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
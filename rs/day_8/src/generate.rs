@@ -0,0 +1,68 @@
+use solution::Rng;
+
+/// The segments lit for each digit on a correctly wired display.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Generates `size` displays, each with its own random wiring, listing all ten
+/// digits in a random order followed by a four digit output.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut digits = (0..10).collect::<Vec<_>>();
+
+    (0..size)
+        .map(|_| {
+            let mut wiring = *b"abcdefg";
+            rng.shuffle(&mut wiring);
+            rng.shuffle(&mut digits);
+
+            let patterns = digits
+                .iter()
+                .map(|&d| pattern(&mut rng, &wiring, d))
+                .collect::<Vec<_>>();
+            let output = (0..4)
+                .map(|_| {
+                    let digit = rng.below(10);
+                    pattern(&mut rng, &wiring, digit)
+                })
+                .collect::<Vec<_>>();
+
+            format!("{} | {}\n", patterns.join(" "), output.join(" "))
+        })
+        .collect()
+}
+
+/// The segments `wiring` lights for `digit`, in a random order.
+fn pattern(rng: &mut Rng, wiring: &[u8; 7], digit: usize) -> String {
+    let mut segments = DIGITS[digit]
+        .bytes()
+        .map(|s| wiring[(s - b'a') as usize] as char)
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut segments);
+    segments.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day8};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day8.parse(&generate(seed, Day8::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day8::INPUT_SIZE, input.len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+}
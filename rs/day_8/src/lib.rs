@@ -1,39 +1,82 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path, str::FromStr};
+use solution::{Answer, ParseError, Solution, Source};
+use std::{path::Path, str::FromStr};
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Display>;
 
-pub const PART_1: usize = 284;
-pub const PART_2: usize = 973499;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day8.parse_file(Path::new(data_dir).join("day_8.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_8.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_8.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day8.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
-        .map(|line| Display::from_str(line).unwrap())
+        .map(|line| Display::parse(&source, line))
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.iter().fold(0usize, |acc, display| {
-        acc + display
-            .output
-            .iter()
-            .filter(|out| matches!(out.count_ones(), 2 | 3 | 4 | 7))
-            .count()
-    })
+pub fn part_1(input: &Input) -> Answer {
+    input
+        .iter()
+        .fold(0usize, |acc, display| {
+            acc + display
+                .output
+                .iter()
+                .filter(|out| matches!(out.count_ones(), 2 | 3 | 4 | 7))
+                .count()
+        })
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
-    input.iter().map(|d| d.decode()).sum()
+pub fn part_2(input: &Input) -> Answer {
+    input.iter().map(|d| d.decode()).sum::<usize>().into()
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Input;
+
+    const DAY: u8 = 8;
+    const INPUT_SIZE: usize = 200;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
@@ -43,32 +86,33 @@ pub struct Display {
 }
 
 impl FromStr for Display {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (displays, output_values) = s.split_once(" | ").unwrap();
-
-        let digits = displays.split_ascii_whitespace().enumerate().fold(
-            [0u8; 10],
-            |mut acc, (idx, digit)| {
-                acc[idx] = digit
-                    .chars()
-                    .fold(0u8, |acc, c| acc | (1 << (c as u8 - b'a')));
-                acc
-            },
-        );
-
-        let output = output_values.split_ascii_whitespace().enumerate().fold(
-            [0u8; 4],
-            |mut acc, (idx, digit)| {
-                acc[idx] = digit
-                    .chars()
-                    .fold(0u8, |acc, c| acc | (1 << (c as u8 - b'a')));
-                acc
-            },
-        );
+        Display::parse(&Source::new(s), s)
+    }
+}
 
-        Ok(Self { digits, output })
+fn parse_patterns(source: &Source, patterns: &str, out: &mut [u8]) -> Result<(), ParseError> {
+    let expected = format!("{} segment patterns", out.len());
+    let mut iter = patterns.split_ascii_whitespace();
+
+    for digit in out.iter_mut() {
+        let pattern = iter
+            .next()
+            .ok_or_else(|| source.error(&patterns[patterns.len()..], &expected))?;
+
+        *digit = pattern
+            .char_indices()
+            .try_fold(0u8, |acc, (idx, c)| match c {
+                'a'..='g' => Ok(acc | (1 << (c as u8 - b'a'))),
+                _ => Err(source.error(&pattern[idx..], "a segment from 'a' to 'g'")),
+            })?;
+    }
+
+    match iter.next() {
+        Some(extra) => Err(source.error(extra, &expected)),
+        None => Ok(()),
     }
 }
 
@@ -86,6 +130,18 @@ impl Display {
         0b1101111, // 9
     ];
 
+    fn parse(source: &Source, line: &str) -> Result<Self, ParseError> {
+        let (displays, output_values) = source.split_once(line, " | ")?;
+
+        let mut digits = [0u8; 10];
+        parse_patterns(source, displays, &mut digits)?;
+
+        let mut output = [0u8; 4];
+        parse_patterns(source, output_values, &mut output)?;
+
+        Ok(Self { digits, output })
+    }
+
     fn decode(&self) -> usize {
         let mut mapping = [0u8; 7];
 
@@ -168,32 +224,38 @@ impl Display {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day8::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -231,8 +293,16 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "1:82: expected 4 segment patterns, found end of input",
+                parse_contents("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd").unwrap_err().to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -241,14 +311,14 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example_1() {
             run(&Case {
                 data: example_1_data(),
-                expected: 0,
+                expected: Answer::Integer(0),
             })
         }
 
@@ -256,18 +326,18 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
         fn example_2() {
             run(&Case {
                 data: example_2_data(),
-                expected: 26,
+                expected: Answer::Integer(26),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day8::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -280,14 +350,14 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example_1() {
             run(&Case {
                 data: example_1_data(),
-                expected: 5353,
+                expected: Answer::Integer(5353),
             })
         }
 
@@ -295,18 +365,18 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
         fn example_2() {
             run(&Case {
                 data: example_2_data(),
-                expected: 61229,
+                expected: Answer::Integer(61229),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day8::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
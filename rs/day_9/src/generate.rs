@@ -0,0 +1,97 @@
+use solution::Rng;
+use std::collections::VecDeque;
+
+/// Generates a `size` by `size` heightmap. Like the real inputs, the map is
+/// split by ridges of 9s into basins of around 50 locations, each sloping down
+/// to a single low point of height 0. Low points are never next to each other,
+/// so each one is lower than all of its neighbours, even on small maps where a
+/// basin is all ridge.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+
+    // Each location belongs to the basin of its closest low point, found by
+    // searching outwards from all of them at once.
+    let mut basins = vec![vec![None; size]; size];
+    let mut queue = VecDeque::new();
+    for idx in 0..(size * size / 50).max(1) {
+        let (x, y) = (rng.below(size), rng.below(size));
+        let crowded = neighbours(x, y, size).any(|(x, y)| basins[y][x].is_some());
+        if basins[y][x].is_none() && !crowded {
+            basins[y][x] = Some((0, idx));
+            queue.push_back((x, y));
+        }
+    }
+    while let Some((x, y)) = queue.pop_front() {
+        let (distance, idx) = basins[y][x].unwrap();
+        for (x, y) in neighbours(x, y, size) {
+            if basins[y][x].is_none() {
+                basins[y][x] = Some((distance + 1, idx));
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    let mut output = String::with_capacity(size * (size + 1));
+    for (y, row) in basins.iter().enumerate() {
+        for (x, &basin) in row.iter().enumerate() {
+            let (distance, idx) = basin.unwrap();
+            let ridge = neighbours(x, y, size).any(|(x, y)| basins[y][x].unwrap().1 != idx);
+            let height = match (distance, ridge) {
+                (0, _) => 0,
+                (_, true) => 9,
+                (_, false) => (distance + rng.below(2)).clamp(1, 8),
+            };
+            output.push((b'0' + height as u8) as char);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn neighbours(x: usize, y: usize, size: usize) -> impl Iterator<Item = (usize, usize)> {
+    [
+        (x.wrapping_sub(1), y),
+        (x + 1, y),
+        (x, y.wrapping_sub(1)),
+        (x, y + 1),
+    ]
+    .into_iter()
+    .filter(move |&(x, y)| x < size && y < size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part_1, part_2, Day9};
+    use solution::Solution;
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(generate(1, 100), generate(1, 100));
+        assert_ne!(generate(1, 100), generate(2, 100));
+    }
+
+    #[test]
+    fn solvable() {
+        for seed in 0..10 {
+            let input = Day9.parse(&generate(seed, Day9::INPUT_SIZE)).unwrap();
+
+            assert_eq!(Day9::INPUT_SIZE, input.len());
+            assert_eq!(Day9::INPUT_SIZE, input[0].len());
+            part_1(&input);
+            part_2(&input);
+        }
+    }
+
+    #[test]
+    fn small() {
+        for size in 1..20 {
+            for seed in 0..10 {
+                let input = Day9.parse(&generate(seed, size)).unwrap();
+
+                part_1(&input);
+                part_2(&input);
+            }
+        }
+    }
+}
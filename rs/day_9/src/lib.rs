@@ -1,63 +1,81 @@
 #![deny(clippy::all)]
-#![feature(test)]
-extern crate test;
 
-use std::{fs::read_to_string, path::Path};
+use solution::{Answer, ParseError, Solution, Source};
+use std::path::Path;
+
+mod generate;
+
+pub use generate::generate;
 
 pub type Input = Vec<Vec<u8>>;
 
-pub const PART_1: usize = 475;
-pub const PART_2: usize = 1092012;
+pub fn read_data(data_dir: &str) -> Result<Input, ParseError> {
+    Day9.parse_file(Path::new(data_dir).join("day_9.txt"))
+}
 
-pub fn read_data(data_dir: &str) -> Input {
-    let contents = read_to_string(Path::new(data_dir).join("day_9.txt")).unwrap();
+#[cfg(feature = "embed-input")]
+pub const EMBEDDED_INPUT: &str = include_str!("../../../data/day_9.txt");
 
-    parse_contents(contents.trim())
+/// Parses the input embedded at compile time, avoiding any I/O.
+#[cfg(feature = "embed-input")]
+pub fn parse_embedded() -> Result<Input, ParseError> {
+    Day9.parse(EMBEDDED_INPUT)
 }
 
-fn parse_contents(contents: &str) -> Input {
+fn parse_contents(contents: &str) -> Result<Input, ParseError> {
+    let source = Source::new(contents);
+    let contents = contents.trim();
+
     contents
         .lines()
-        .map(|line| line.chars().map(|c| c as u8 - b'0').collect())
+        .map(|line| {
+            line.char_indices()
+                .map(|(idx, _)| source.digit(&line[idx..]))
+                .collect()
+        })
         .collect()
 }
 
-pub fn part_1(input: &Input) -> usize {
-    input.iter().enumerate().fold(0usize, |acc, (y, row)| {
-        acc + row
-            .iter()
-            .enumerate()
-            .fold(0usize, |row_acc, (x, &height)| {
-                if y > 0 {
-                    if let Some(up) = input.get(y - 1).and_then(|prev_row| prev_row.get(x)) {
-                        if height.ge(up) {
+pub fn part_1(input: &Input) -> Answer {
+    input
+        .iter()
+        .enumerate()
+        .fold(0usize, |acc, (y, row)| {
+            acc + row
+                .iter()
+                .enumerate()
+                .fold(0usize, |row_acc, (x, &height)| {
+                    if y > 0 {
+                        if let Some(up) = input.get(y - 1).and_then(|prev_row| prev_row.get(x)) {
+                            if height.ge(up) {
+                                return row_acc;
+                            }
+                        }
+                    }
+                    if let Some(down) = input.get(y + 1).and_then(|prev_row| prev_row.get(x)) {
+                        if height.ge(down) {
                             return row_acc;
                         }
                     }
-                }
-                if let Some(down) = input.get(y + 1).and_then(|prev_row| prev_row.get(x)) {
-                    if height.ge(down) {
-                        return row_acc;
+                    if x > 0 {
+                        if let Some(left) = row.get(x - 1) {
+                            if height.ge(left) {
+                                return row_acc;
+                            }
+                        }
                     }
-                }
-                if x > 0 {
-                    if let Some(left) = row.get(x - 1) {
-                        if height.ge(left) {
+                    if let Some(right) = row.get(x + 1) {
+                        if height.ge(right) {
                             return row_acc;
                         }
                     }
-                }
-                if let Some(right) = row.get(x + 1) {
-                    if height.ge(right) {
-                        return row_acc;
-                    }
-                }
-                row_acc + height as usize + 1
-            })
-    })
+                    row_acc + height as usize + 1
+                })
+        })
+        .into()
 }
 
-pub fn part_2(input: &Input) -> usize {
+pub fn part_2(input: &Input) -> Answer {
     let minima = find_minima(input);
 
     let mut heights: Vec<Vec<u8>> = input.to_vec();
@@ -115,7 +133,37 @@ pub fn part_2(input: &Input) -> usize {
         .collect();
 
     basin_sizes.sort_unstable();
-    basin_sizes.iter().rev().take(3).product()
+    basin_sizes.iter().rev().take(3).product::<usize>().into()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Input;
+
+    const DAY: u8 = 9;
+    const INPUT_SIZE: usize = 100;
+
+    fn parse(&self, contents: &str) -> Result<Input, ParseError> {
+        parse_contents(contents)
+    }
+
+    #[cfg(feature = "embed-input")]
+    fn embedded_input(&self) -> Option<&'static str> {
+        Some(EMBEDDED_INPUT)
+    }
+
+    fn part_1(&self, input: &Input) -> Answer {
+        part_1(input)
+    }
+
+    fn part_2(&self, input: &Input) -> Answer {
+        part_2(input)
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        generate(seed, size)
+    }
 }
 
 fn find_minima(input: &Input) -> Vec<(usize, usize)> {
@@ -165,32 +213,38 @@ fn find_minima(input: &Input) -> Vec<(usize, usize)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use test::Bencher;
+    use solution::Answers;
 
     mod total {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
-                assert_ne!(data, Input::default());
-                assert_eq!(PART_1, part_1(&data));
-                assert_eq!(PART_2, part_2(&data));
-            })
+        #[test]
+        fn actual() {
+            let expected = Answers::expected("../../data", Day9::DAY).unwrap();
+
+            let data = read_data("../../data").unwrap();
+            assert_ne!(data, Input::default());
+            assert_eq!(expected.part_1, part_1(&data));
+            assert_eq!(expected.part_2, part_2(&data));
         }
     }
 
     mod read_data {
         use super::*;
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
-            b.iter(|| {
-                let data = read_data("../../data");
+        #[test]
+        fn actual() {
+            let data = read_data("../../data").unwrap();
 
-                assert_ne!(data, Input::default())
-            })
+            assert_ne!(data, Input::default())
+        }
+
+        #[cfg(feature = "embed-input")]
+        #[test]
+        fn embedded() {
+            let data = parse_embedded().unwrap();
+
+            assert_ne!(data, Input::default())
         }
     }
 
@@ -214,8 +268,21 @@ mod tests {
             })
         }
 
+        #[test]
+        fn malformed() {
+            assert_eq!(
+                "2:3: expected a digit, found \"x8\"",
+                parse_contents(
+                    "2199
+39x8"
+                )
+                .unwrap_err()
+                .to_string()
+            )
+        }
+
         fn run(test: &Case) {
-            assert_eq!(test.expected, parse_contents(test.input))
+            assert_eq!(test.expected, parse_contents(test.input).unwrap())
         }
     }
 
@@ -224,25 +291,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 15,
+                expected: Answer::Integer(15),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_1,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day9::DAY).unwrap().part_1,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -255,25 +322,25 @@ mod tests {
 
         struct Case {
             data: Input,
-            expected: usize,
+            expected: Answer,
         }
 
         #[test]
         fn example() {
             run(&Case {
                 data: example_data(),
-                expected: 1134,
+                expected: Answer::Integer(1134),
             })
         }
 
-        #[bench]
-        fn actual(b: &mut Bencher) {
+        #[test]
+        fn actual() {
             let case = Case {
-                data: read_data("../../data"),
-                expected: PART_2,
+                data: read_data("../../data").unwrap(),
+                expected: Answers::expected("../../data", Day9::DAY).unwrap().part_2,
             };
 
-            b.iter(|| run(&case))
+            run(&case)
         }
 
         fn run(test: &Case) {
@@ -0,0 +1,291 @@
+use solution::{Answer, Answers, DynSolution, ParseError};
+use std::{
+    any::Any,
+    fmt,
+    fs::{read_dir, read_to_string},
+    io,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+/// The name given to the inputs stored directly in the data directory.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named set of inputs, i.e. a directory of `day_N.txt` files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+/// Finds the profiles in `data_dir`: the directory itself is the
+/// [`DEFAULT_PROFILE`], and every subdirectory containing inputs is a profile
+/// named after that subdirectory, e.g. `data/alice/day_15.txt`. Only inputs
+/// directly within a subdirectory count, so other events' inputs in
+/// `data/events/<year>` and the examples in `data/examples/day_<day>` don't.
+pub fn profiles(data_dir: &Path) -> io::Result<Vec<Profile>> {
+    let mut profiles = Vec::new();
+    if has_inputs(data_dir)? {
+        profiles.push(Profile {
+            name: DEFAULT_PROFILE.to_owned(),
+            dir: data_dir.to_owned(),
+        });
+    }
+
+    let mut dirs = read_dir(data_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    dirs.sort();
+
+    for dir in dirs {
+        if dir.is_dir() && has_inputs(&dir)? {
+            profiles.push(Profile {
+                name: dir.file_name().unwrap().to_string_lossy().into_owned(),
+                dir,
+            });
+        }
+    }
+
+    Ok(profiles)
+}
+
+fn has_inputs(dir: &Path) -> io::Result<bool> {
+    for entry in read_dir(dir)? {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with("day_") && name.ends_with(".txt") {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// The outcome of checking one day against one profile's input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    /// There is no recorded answer for the input.
+    Missing,
+    /// The profile has no input for the day.
+    NoInput,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail(_) => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+            Status::NoInput => write!(f, "-"),
+        }
+    }
+}
+
+/// Solves `profile`'s input for the day and compares it with the recorded
+/// answers. Parse errors and panics are reported as failures.
+pub fn check(solution: &dyn DynSolution, profile: &Profile, answers: &Answers) -> Status {
+    let file = profile.dir.join(format!("day_{}.txt", solution.day()));
+    let contents = match read_to_string(&file) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Status::NoInput,
+        Err(e) => return Status::Fail(format!("could not read {}: {}", file.display(), e)),
+    };
+
+    let expected = match answers.get(solution.day(), &contents) {
+        Some(expected) => expected,
+        None => return Status::Missing,
+    };
+
+    let solved = catch_unwind(AssertUnwindSafe(
+        || -> Result<(Answer, Answer), ParseError> {
+            let input = solution.parse(&contents)?;
+            Ok((
+                solution.part_1(input.as_ref()),
+                solution.part_2(input.as_ref()),
+            ))
+        },
+    ));
+
+    let (part_1, part_2) = match solved {
+        Ok(Ok(answers)) => answers,
+        Ok(Err(e)) => return Status::Fail(e.in_file(&file).to_string()),
+        Err(panic) => return Status::Fail(format!("panicked: {}", panic_message(&panic))),
+    };
+
+    let mismatches = [(1, &expected.part_1, part_1), (2, &expected.part_2, part_2)]
+        .into_iter()
+        .filter(|(_, expected, found)| *expected != found)
+        .map(|(part, expected, found)| {
+            format!("part {}: expected {}, found {}", part, expected, found)
+        })
+        .collect::<Vec<_>>();
+
+    if mismatches.is_empty() {
+        Status::Pass
+    } else {
+        Status::Fail(mismatches.join("; "))
+    }
+}
+
+fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::Expected;
+    use std::{
+        env::temp_dir,
+        fs::{create_dir_all, remove_dir_all, write},
+        process,
+    };
+
+    const INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+
+    /// A scratch data directory, removed when dropped.
+    struct DataDir(PathBuf);
+
+    impl DataDir {
+        fn new(name: &str) -> Self {
+            let dir = temp_dir().join(format!("aoc-check-{}-{}", process::id(), name));
+            create_dir_all(&dir).unwrap();
+            DataDir(dir)
+        }
+
+        fn write(&self, file: &str, contents: &str) {
+            let path = self.0.join(file);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, contents).unwrap();
+        }
+
+        fn profile(&self) -> Profile {
+            Profile {
+                name: DEFAULT_PROFILE.to_owned(),
+                dir: self.0.clone(),
+            }
+        }
+    }
+
+    impl Drop for DataDir {
+        fn drop(&mut self) {
+            let _ = remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn profiles() {
+        let data = DataDir::new("profiles");
+        data.write("day_1.txt", INPUT);
+        data.write("bob/day_1.txt", INPUT);
+        data.write("alice/day_15.txt", INPUT);
+        data.write("notes/README.md", "");
+        // Another event's input, as fetch_day saves it, and example fixtures.
+        data.write("events/2022/day_1.txt", INPUT);
+        data.write("examples/day_1/1.txt", INPUT);
+
+        let names = super::profiles(&data.0)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![DEFAULT_PROFILE, "alice", "bob"], names);
+    }
+
+    mod check {
+        use super::*;
+
+        struct Case<'c> {
+            name: &'c str,
+            input: Option<&'c str>,
+            expected: Option<Expected>,
+            status: Status,
+        }
+
+        #[test]
+        fn pass() {
+            run(&Case {
+                name: "pass",
+                input: Some(INPUT),
+                expected: Some(Expected {
+                    part_1: Answer::Integer(7),
+                    part_2: Answer::Integer(5),
+                }),
+                status: Status::Pass,
+            })
+        }
+
+        #[test]
+        fn wrong_answer() {
+            run(&Case {
+                name: "wrong_answer",
+                input: Some(INPUT),
+                expected: Some(Expected {
+                    part_1: Answer::Integer(7),
+                    part_2: Answer::Integer(6),
+                }),
+                status: Status::Fail("part 2: expected 6, found 5".to_owned()),
+            })
+        }
+
+        #[test]
+        fn parse_error() {
+            run(&Case {
+                name: "parse_error",
+                input: Some("199\nabc"),
+                expected: Some(Expected::default()),
+                status: Status::Fail("day_1.txt:2:1: expected usize, found \"abc\"".to_owned()),
+            })
+        }
+
+        #[test]
+        fn missing() {
+            run(&Case {
+                name: "missing",
+                input: Some(INPUT),
+                expected: None,
+                status: Status::Missing,
+            })
+        }
+
+        #[test]
+        fn no_input() {
+            run(&Case {
+                name: "no_input",
+                input: None,
+                expected: None,
+                status: Status::NoInput,
+            })
+        }
+
+        fn run(test: &Case) {
+            let data = DataDir::new(test.name);
+            let mut answers = Answers::default();
+            if let Some(input) = test.input {
+                data.write("day_1.txt", input);
+                if let Some(expected) = &test.expected {
+                    answers.insert(1, input, expected.clone());
+                }
+            }
+
+            let status = super::check(&day_1::Day1, &data.profile(), &answers);
+
+            match (&test.status, status) {
+                // The path of the scratch directory varies, so only compare the end.
+                (Status::Fail(expected), Status::Fail(found)) => assert!(
+                    found.ends_with(expected),
+                    "expected failure ending with {:?}, found {:?}",
+                    expected,
+                    found
+                ),
+                (expected, found) => assert_eq!(expected, &found),
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+#![deny(clippy::all)]
+use solution::DynSolution;
+use std::{ops::RangeInclusive, str::FromStr};
+
+pub mod check;
+
+/// Every implemented day, in order.
+pub const SOLUTIONS: &[&dyn DynSolution] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+    &day_16::Day16,
+    &day_17::Day17,
+    &day_19::Day19,
+    &day_20::Day20,
+    &day_21::Day21,
+    &day_22::Day22,
+    &day_25::Day25,
+];
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().find(|s| s.day() == day).copied()
+}
+
+/// A set of days given on the command line, e.g. `all`, `15`, `3-7` or `1,4,10-12`.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Days {
+    All,
+    Ranges(Vec<RangeInclusive<u8>>),
+}
+
+impl Days {
+    pub fn solutions(&self) -> Vec<&'static dyn DynSolution> {
+        SOLUTIONS
+            .iter()
+            .filter(|s| self.contains(s.day()))
+            .copied()
+            .collect()
+    }
+
+    pub fn contains(&self, day: u8) -> bool {
+        match self {
+            Days::All => true,
+            Days::Ranges(ranges) => ranges.iter().any(|r| r.contains(&day)),
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("all") {
+            return Ok(Days::All);
+        }
+
+        let parse_day = |day: &str| match day.trim().parse::<u8>() {
+            Ok(d) if (1..=25).contains(&d) => Ok(d),
+            _ => Err(format!("invalid day: {}", day)),
+        };
+
+        s.split(',')
+            .map(|part| match part.split_once('-') {
+                Some((from, to)) => {
+                    let (from, to) = (parse_day(from)?, parse_day(to)?);
+                    if from > to {
+                        return Err(format!("invalid day range: {}", part));
+                    }
+                    Ok(from..=to)
+                }
+                None => parse_day(part).map(|d| d..=d),
+            })
+            .collect::<Result<_, _>>()
+            .map(Days::Ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod days {
+        use super::*;
+
+        struct Case<'c> {
+            input: &'c str,
+            expected: Result<Days, String>,
+        }
+
+        #[test]
+        fn all() {
+            run(&Case {
+                input: "all",
+                expected: Ok(Days::All),
+            })
+        }
+
+        #[test]
+        fn single() {
+            run(&Case {
+                input: "15",
+                expected: Ok(Days::Ranges(vec![15..=15])),
+            })
+        }
+
+        #[test]
+        fn list() {
+            run(&Case {
+                input: "1,4,10-12",
+                expected: Ok(Days::Ranges(vec![1..=1, 4..=4, 10..=12])),
+            })
+        }
+
+        #[test]
+        fn out_of_range() {
+            run(&Case {
+                input: "26",
+                expected: Err("invalid day: 26".to_string()),
+            })
+        }
+
+        #[test]
+        fn backwards_range() {
+            run(&Case {
+                input: "7-3",
+                expected: Err("invalid day range: 7-3".to_string()),
+            })
+        }
+
+        #[test]
+        fn solutions() {
+            let days = Days::from_str("17-20").unwrap();
+
+            assert_eq!(
+                vec![17, 19, 20],
+                days.solutions()
+                    .iter()
+                    .map(|s| s.day())
+                    .collect::<Vec<u8>>()
+            );
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, Days::from_str(test.input))
+        }
+    }
+}
@@ -0,0 +1,362 @@
+#![deny(clippy::all)]
+use aoc::{
+    check::{check, profiles, Status},
+    solution, Days,
+};
+use clap::Parser;
+use solution::{Answers, AnswersError, DynSolution, ANSWERS_FILE};
+use std::{
+    fs::read_to_string,
+    io::{self, Read},
+    panic,
+    path::{Path, PathBuf},
+    process::exit,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(clap::Parser, Debug)]
+struct Opts {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    #[clap(about = "Solve one or more days, printing each answer and how long it took")]
+    Run(RunOpts),
+    #[clap(
+        about = "Check days against the recorded answers for every input profile, printing a pass/fail/missing matrix"
+    )]
+    Check(CheckOpts),
+    #[clap(
+        about = "Print a random valid input for a day, e.g. to pipe into 'aoc run' for stress testing"
+    )]
+    Generate(GenerateOpts),
+}
+
+#[derive(clap::Args, Debug)]
+struct RunOpts {
+    #[clap(
+        value_name = "DAYS",
+        default_value = "all",
+        help = "Days to run: 'all', a single day, a range such as '3-7', or a comma-separated list of these"
+    )]
+    days: Days,
+
+    #[clap(
+        short,
+        long,
+        possible_values = ["1", "2"],
+        help = "Only run the given part"
+    )]
+    part: Option<u8>,
+
+    #[clap(
+        short,
+        long,
+        help = "Input file to use instead of '<DATA_DIR>/day_<DAY>.txt', or '-' for stdin. Only valid for a single day. \
+                Input piped to a single day is used without this"
+    )]
+    input: Option<PathBuf>,
+
+    #[clap(
+        long,
+        help = "Directory containing the 'day_<DAY>.txt' input files [default: ./data, or the inputs \
+                embedded at compile time when built with the 'embed-input' feature]"
+    )]
+    data_dir: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct CheckOpts {
+    #[clap(
+        value_name = "DAYS",
+        default_value = "all",
+        help = "Days to check: 'all', a single day, a range such as '3-7', or a comma-separated list of these"
+    )]
+    days: Days,
+
+    #[clap(
+        long = "profile",
+        value_name = "PROFILE",
+        multiple_occurrences = true,
+        help = "Only check the given profile. May be repeated"
+    )]
+    profiles: Vec<String>,
+
+    #[clap(
+        long,
+        default_value = "./data",
+        help = "Directory containing 'answers.toml', the default profile's inputs, and a subdirectory per other profile"
+    )]
+    data_dir: PathBuf,
+}
+
+#[derive(clap::Args, Debug)]
+struct GenerateOpts {
+    #[clap(value_name = "DAY", help = "Day to generate an input for")]
+    day: u8,
+
+    #[clap(
+        long,
+        help = "Seed for the generator. The same seed and size always give the same input \
+                [default: taken from the clock and printed to stderr]"
+    )]
+    seed: Option<u64>,
+
+    #[clap(
+        long,
+        help = "How large an input to generate, in whatever unit suits the day, such as lines, \
+                grid width or scanners. Day 11 only accepts 10 [default: the scale of the real inputs]"
+    )]
+    size: Option<usize>,
+}
+
+fn main() {
+    let opts = Opts::parse();
+
+    match opts.command {
+        Command::Run(opts) => run(&opts),
+        Command::Check(opts) => check_all(&opts),
+        Command::Generate(opts) => generate(&opts),
+    }
+}
+
+fn run(opts: &RunOpts) {
+    let solutions = opts.days.solutions();
+    if solutions.is_empty() {
+        eprintln!("no solutions found for the selected days");
+        exit(1);
+    }
+    if opts.input.is_some() && solutions.len() > 1 {
+        eprintln!("--input can only be used when running a single day");
+        exit(1);
+    }
+
+    let piped = match &opts.input {
+        None if solutions.len() == 1 => read_piped_stdin(),
+        _ => None,
+    };
+
+    let mut failed = false;
+    for solution in solutions {
+        let input = match (&opts.input, &piped) {
+            (Some(file), _) if file.as_os_str() == STDIN => read_stdin(),
+            (Some(file), _) => read_file(file),
+            (None, Some(contents)) => Ok((PathBuf::from(STDIN_NAME), contents.to_owned())),
+            (None, None) => match (&opts.data_dir, solution.embedded_input()) {
+                (None, Some(embedded)) => Ok((
+                    PathBuf::from(format!("<embedded day_{}.txt>", solution.day())),
+                    embedded.to_owned(),
+                )),
+                (data_dir, _) => {
+                    let data_dir = data_dir.as_deref().unwrap_or(Path::new(DEFAULT_DATA_DIR));
+                    read_file(&input_path(data_dir, solution.day()))
+                }
+            },
+        };
+
+        let result =
+            input.and_then(|(origin, contents)| run_day(solution, &origin, &contents, opts.part));
+        if let Err(e) = result {
+            eprintln!("Day {}: {}\n", solution.day(), e);
+            failed = true;
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
+
+const DEFAULT_DATA_DIR: &str = "./data";
+/// The `--input` value that reads from stdin.
+const STDIN: &str = "-";
+/// Stands in for a file name in errors about input read from stdin.
+const STDIN_NAME: &str = "<stdin>";
+
+fn read_file(file: &Path) -> Result<(PathBuf, String), String> {
+    read_to_string(file)
+        .map(|contents| (file.to_owned(), contents))
+        .map_err(|e| format!("could not read {}: {}", file.display(), e))
+}
+
+fn read_stdin() -> Result<(PathBuf, String), String> {
+    let mut contents = String::new();
+    io::stdin()
+        .read_to_string(&mut contents)
+        .map_err(|e| format!("could not read {}: {}", STDIN_NAME, e))?;
+    Ok((PathBuf::from(STDIN_NAME), contents))
+}
+
+/// Reads stdin if something is being piped or redirected in, e.g.
+/// `cat input | aoc run 9` or `aoc run 9 < input`. Any other handle, such as
+/// a terminal, a socket or `/dev/null`, is left alone: under CI or an IDE
+/// runner it may never be closed, and reading it would hang.
+fn read_piped_stdin() -> Option<String> {
+    if !stdin_is_piped() {
+        return None;
+    }
+
+    read_stdin()
+        .ok()
+        .map(|(_, contents)| contents)
+        .filter(|contents| !contents.trim().is_empty())
+}
+
+/// Whether stdin is a pipe or a regular file.
+#[cfg(unix)]
+fn stdin_is_piped() -> bool {
+    use std::{fs::File, os::fd::AsFd, os::unix::fs::FileTypeExt};
+
+    io::stdin()
+        .as_fd()
+        .try_clone_to_owned()
+        .and_then(|fd| File::from(fd).metadata())
+        .map(|metadata| metadata.is_file() || metadata.file_type().is_fifo())
+        .unwrap_or(false)
+}
+
+/// Elsewhere, input is only read from stdin when asked for with `--input -`.
+#[cfg(not(unix))]
+fn stdin_is_piped() -> bool {
+    false
+}
+
+fn run_day(
+    solution: &dyn DynSolution,
+    origin: &Path,
+    contents: &str,
+    part: Option<u8>,
+) -> Result<(), String> {
+    println!("Day {}", solution.day());
+
+    let (input, elapsed) = timed(|| solution.parse(contents));
+    let input = input.map_err(|e| e.in_file(origin).to_string())?;
+    println!("\tParse:\t({:#?})", elapsed);
+
+    if part != Some(2) {
+        let (answer, elapsed) = timed(|| solution.part_1(input.as_ref()));
+        println!("\tPart 1:\t{}\t({:#?})", answer, elapsed);
+    }
+
+    if part != Some(1) {
+        let (answer, elapsed) = timed(|| solution.part_2(input.as_ref()));
+        println!("\tPart 2:\t{}\t({:#?})", answer, elapsed);
+    }
+
+    println!();
+    Ok(())
+}
+
+fn check_all(opts: &CheckOpts) {
+    let answers = match Answers::load(&opts.data_dir.join(ANSWERS_FILE)) {
+        Ok(answers) => answers,
+        Err(AnswersError::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+            Answers::default()
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+
+    let profiles = match profiles(&opts.data_dir) {
+        Ok(profiles) => profiles
+            .into_iter()
+            .filter(|p| opts.profiles.is_empty() || opts.profiles.contains(&p.name))
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            eprintln!("could not read {}: {}", opts.data_dir.display(), e);
+            exit(1);
+        }
+    };
+    if profiles.is_empty() {
+        eprintln!("no input profiles found in {}", opts.data_dir.display());
+        exit(1);
+    }
+
+    // Panics are reported as failures in the matrix instead.
+    panic::set_hook(Box::new(|_| {}));
+
+    let width = profiles.iter().map(|p| p.name.len()).max().unwrap().max(7);
+    let header = profiles
+        .iter()
+        .fold(format!("{:<6}", "Day"), |row, profile| {
+            format!("{}  {:<width$}", row, profile.name, width = width)
+        });
+    println!("{}", header.trim_end());
+
+    let mut failures = Vec::new();
+    for solution in opts.days.solutions() {
+        let mut row = format!("{:<6}", solution.day());
+        for profile in &profiles {
+            let status = check(solution, profile, &answers);
+            row = format!("{}  {:<width$}", row, status.to_string(), width = width);
+            if let Status::Fail(reason) = status {
+                failures.push(format!(
+                    "Day {} ({}): {}",
+                    solution.day(),
+                    profile.name,
+                    reason
+                ));
+            }
+        }
+        println!("{}", row.trim_end());
+    }
+
+    if !failures.is_empty() {
+        println!();
+        failures.iter().for_each(|f| println!("{}", f));
+        exit(1);
+    }
+}
+
+fn generate(opts: &GenerateOpts) {
+    let solution = match solution(opts.day) {
+        Some(solution) => solution,
+        None => {
+            eprintln!("no solution found for day {}", opts.day);
+            exit(1);
+        }
+    };
+
+    let size = opts.size.unwrap_or_else(|| solution.input_size());
+    let sizes = solution.input_sizes();
+    if !sizes.contains(&size) {
+        if sizes.start() == sizes.end() {
+            eprintln!(
+                "day {} can only generate a size of {}",
+                opts.day,
+                sizes.start()
+            );
+        } else {
+            eprintln!(
+                "day {} needs a size of at least {}",
+                opts.day,
+                sizes.start()
+            );
+        }
+        exit(1);
+    }
+
+    let seed = opts.seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        eprintln!("seed: {}", seed);
+        seed
+    });
+
+    print!("{}", solution.generate(seed, size));
+}
+
+fn input_path(data_dir: &Path, day: u8) -> PathBuf {
+    data_dir.join(format!("day_{}.txt", day))
+}
+
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let out = f();
+    (out, start.elapsed())
+}
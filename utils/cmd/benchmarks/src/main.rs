@@ -83,8 +83,8 @@ fn main() {
                 "  <tr>\n    <td>{}</td>\n{}  </tr>",
                 day,
                 diffs.iter().flatten().fold(String::new(), |mut acc, diff| {
-                    acc.push_str(&diff);
-                    acc.push_str("\n");
+                    acc.push_str(diff);
+                    acc.push('\n');
                     acc
                 })
             ));
@@ -139,24 +139,25 @@ fn percent_diff(dur: Option<Duration>, min: Duration) -> String {
 
 fn bench_rust(day: usize) -> [[Option<Duration>; 4]; 25] {
     let mut cmd = Command::new("cargo");
-    let bench = if day > 0 {
-        cmd.args(["bench", "--package", &format!("day_{}", day)])
+    let mut bench = if day > 0 {
+        let package = format!("day_{}", day);
+        cmd.args(["bench", "--package", &package, "--bench", &package])
     } else {
-        cmd.args(["bench", "--package", "day_*"])
+        cmd.args(["bench", "--workspace", "--bench", "day_*"])
     }
     .stdout(Stdio::piped())
     .stderr(Stdio::piped())
     .spawn()
     .unwrap();
 
-    let stdout_reader = BufReader::new(bench.stdout.unwrap());
-    let stderr_reader = BufReader::new(bench.stderr.unwrap());
+    let stdout_reader = BufReader::new(bench.stdout.take().unwrap());
+    let stderr_reader = BufReader::new(bench.stderr.take().unwrap());
 
     let (day_send, day_recv): (Sender<usize>, Receiver<usize>) = mpsc::channel();
     let s = thread::spawn(move || {
         stderr_reader
             .lines()
-            .filter_map(|line| line.ok())
+            .map_while(Result::ok)
             .for_each(|line| {
                 println!("{}", line);
                 if let Some(c) = Regex::new(r".*/deps/day_(\d+).*").unwrap().captures(&line) {
@@ -167,58 +168,46 @@ fn bench_rust(day: usize) -> [[Option<Duration>; 4]; 25] {
             });
     });
 
+    // Criterion reports the lower bound, estimate and upper bound of each
+    // benchmark's time, e.g. `part_1  time:   [1.0402 µs 1.0431 µs 1.0463 µs]`.
+    let time_regex =
+        Regex::new(r"^([_\w]+)\s+time:\s+\[[\d.]+ \S+ ([\d.]+) (\S+) [\d.]+ \S+\]").unwrap();
+
     let mut day = None;
     let mut results = [[None; 4]; 25];
     stdout_reader
         .lines()
-        .filter_map(|line| line.ok())
+        .map_while(Result::ok)
         .for_each(|line| {
             println!("{}", line);
-            if !line.contains(" bench: ") {
-                return;
-            }
-            Regex::new(r".*?tests::([_\w]+):.*bench:\s+([\d,]+)")
-                .unwrap()
-                .captures_iter(&line)
-                .for_each(|c| {
-                    let part = match c.get(1).unwrap().as_str() {
-                        "read_data" => 0,
-                        "part_1" => 1,
-                        "part_2" => 2,
-                        _ => return,
-                    };
-                    let time = Duration::from_nanos(
-                        c.get(2)
-                            .unwrap()
-                            .as_str()
-                            .chars()
-                            .filter_map(|c| {
-                                if c.is_numeric() {
-                                    Some(c as u8 - b'0')
-                                } else {
-                                    None
-                                }
-                            })
-                            .fold(0u64, |acc, n| (acc * 10) + n as u64),
-                    );
-
-                    let idx = match day {
-                        Some(day) => day - 1,
-                        None => {
-                            let new_day = day_recv.recv().unwrap();
-                            day = Some(new_day);
-                            new_day - 1
-                        }
-                    };
-
-                    results[idx][part] = Some(time);
-                    if results[idx].iter().filter(|res| res.is_none()).count() == 1 {
-                        day = None;
+            time_regex.captures_iter(&line).for_each(|c| {
+                let part = match c.get(1).unwrap().as_str() {
+                    "read_data" => 0,
+                    "part_1" => 1,
+                    "part_2" => 2,
+                    _ => return,
+                };
+                let time =
+                    criterion_duration(c.get(2).unwrap().as_str(), c.get(3).unwrap().as_str());
+
+                let idx = match day {
+                    Some(day) => day - 1,
+                    None => {
+                        let new_day = day_recv.recv().unwrap();
+                        day = Some(new_day);
+                        new_day - 1
                     }
-                });
+                };
+
+                results[idx][part] = Some(time);
+                if results[idx].iter().filter(|res| res.is_none()).count() == 1 {
+                    day = None;
+                }
+            });
         });
 
     s.join().unwrap();
+    bench.wait().unwrap();
 
     results.iter_mut().for_each(|day| {
         day[3] = day[..3]
@@ -229,6 +218,20 @@ fn bench_rust(day: usize) -> [[Option<Duration>; 4]; 25] {
     results
 }
 
+/// Converts a time printed by criterion, such as `1.0431 µs`, to a duration.
+fn criterion_duration(value: &str, unit: &str) -> Duration {
+    let value = value.parse::<f64>().unwrap();
+    let nanos = match unit {
+        "ps" => value / 1_000.0,
+        "ns" => value,
+        "µs" | "us" => value * 1_000.0,
+        "ms" => value * 1_000_000.0,
+        "s" => value * 1_000_000_000.0,
+        _ => panic!("unknown time unit: {}", unit),
+    };
+    Duration::from_nanos(nanos.round() as u64)
+}
+
 fn bench_go(day: usize) -> [[Option<Duration>; 4]; 25] {
     let mut cmd = Command::new("go");
     let output = if day > 0 {
@@ -248,46 +251,43 @@ fn bench_go(day: usize) -> [[Option<Duration>; 4]; 25] {
 
     let mut results = [[None; 4]; 25];
     let mut day = 0usize;
-    reader
-        .lines()
-        .filter_map(|line| line.ok())
-        .for_each(|line| {
-            println!("{}", line);
-            if line.starts_with("pkg:") {
-                day = Regex::new(r".*/day_(\d+)")
-                    .unwrap()
-                    .captures(&line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .parse::<usize>()
-                    .unwrap();
-                return;
-            }
-            if !line.starts_with("Benchmark") {
-                return;
-            }
-            Regex::new(r"Benchmark(\w+)-\d+\s+\d+\s+([\d\.]+) ns/op")
+    reader.lines().map_while(Result::ok).for_each(|line| {
+        println!("{}", line);
+        if line.starts_with("pkg:") {
+            day = Regex::new(r".*/day_(\d+)")
                 .unwrap()
-                .captures_iter(&line)
-                .for_each(|c| {
-                    let part = match c.get(1).unwrap().as_str() {
-                        "ReadData" => 0,
-                        "Part1" => 1,
-                        "Part2" => 2,
-                        _ => return,
-                    };
-                    let time = Duration::from_nanos(
-                        c.get(2).unwrap().as_str().parse::<f64>().unwrap().round() as u64,
-                    );
-
-                    results[day - 1][part] = Some(time);
-                });
-
-            output.push_str(&line);
-            output.push('\n');
-        });
+                .captures(&line)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .as_str()
+                .parse::<usize>()
+                .unwrap();
+            return;
+        }
+        if !line.starts_with("Benchmark") {
+            return;
+        }
+        Regex::new(r"Benchmark(\w+)-\d+\s+\d+\s+([\d\.]+) ns/op")
+            .unwrap()
+            .captures_iter(&line)
+            .for_each(|c| {
+                let part = match c.get(1).unwrap().as_str() {
+                    "ReadData" => 0,
+                    "Part1" => 1,
+                    "Part2" => 2,
+                    _ => return,
+                };
+                let time = Duration::from_nanos(
+                    c.get(2).unwrap().as_str().parse::<f64>().unwrap().round() as u64,
+                );
+
+                results[day - 1][part] = Some(time);
+            });
+
+        output.push_str(&line);
+        output.push('\n');
+    });
 
     results.iter_mut().for_each(|day| {
         day[3] = day[..3]
@@ -322,65 +322,62 @@ fn bench_deno(day: usize) -> [[Option<Duration>; 4]; 25] {
     let mut results = [[None; 4]; 25];
     let mut part = 0usize;
     let mut times = [None; 4];
-    reader
-        .lines()
-        .filter_map(|line| line.ok())
-        .for_each(|line| {
-            println!("{}", line);
-            if !line.ends_with("benchmarks ...") && line.starts_with("running ") {
-                let day = Regex::new(r"running \d tests.*/day_(\d+)/main.test.ts")
-                    .unwrap()
-                    .captures(&line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .parse::<usize>()
-                    .unwrap()
-                    - 1;
-                times[3] = times[..3]
-                    .iter()
-                    .filter_map(|&d| d)
-                    .reduce(|prev, t| prev + t);
-                results[day] = times;
-                times = [None; 4];
-                return;
-            }
-
-            if line.starts_with("benchmark ") {
-                part = match Regex::new(r"benchmark ([\w\d ]+).*")
-                    .unwrap()
-                    .captures(&line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                {
-                    "read data " => 0,
-                    "part 1 " => 1,
-                    "part 2 " => 2,
-                    _ => return,
-                };
-                return;
-            }
-            if !line.contains("runs avg:") {
-                return;
-            }
-
-            times[part] = Some(Duration::from_nanos(
-                (Regex::new(r".*: ([\d\.]+)ms")
-                    .unwrap()
-                    .captures(&line)
-                    .unwrap()
-                    .get(1)
-                    .unwrap()
-                    .as_str()
-                    .parse::<f64>()
-                    .unwrap()
-                    * 1_000_000.0)
-                    .round() as u64,
-            ));
-        });
+    reader.lines().map_while(Result::ok).for_each(|line| {
+        println!("{}", line);
+        if !line.ends_with("benchmarks ...") && line.starts_with("running ") {
+            let day = Regex::new(r"running \d tests.*/day_(\d+)/main.test.ts")
+                .unwrap()
+                .captures(&line)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .as_str()
+                .parse::<usize>()
+                .unwrap()
+                - 1;
+            times[3] = times[..3]
+                .iter()
+                .filter_map(|&d| d)
+                .reduce(|prev, t| prev + t);
+            results[day] = times;
+            times = [None; 4];
+            return;
+        }
+
+        if line.starts_with("benchmark ") {
+            part = match Regex::new(r"benchmark ([\w\d ]+).*")
+                .unwrap()
+                .captures(&line)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .as_str()
+            {
+                "read data " => 0,
+                "part 1 " => 1,
+                "part 2 " => 2,
+                _ => return,
+            };
+            return;
+        }
+        if !line.contains("runs avg:") {
+            return;
+        }
+
+        times[part] = Some(Duration::from_nanos(
+            (Regex::new(r".*: ([\d\.]+)ms")
+                .unwrap()
+                .captures(&line)
+                .unwrap()
+                .get(1)
+                .unwrap()
+                .as_str()
+                .parse::<f64>()
+                .unwrap()
+                * 1_000_000.0)
+                .round() as u64,
+        ));
+    });
 
     results
 }
@@ -0,0 +1,199 @@
+//! Named sets of earlier results, to compare a run against.
+
+use crate::stats::{mann_whitney, Stats};
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs::{copy, create_dir_all, read_to_string},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Where baselines are kept, within the results directory.
+pub const BASELINES_DIR: &str = "baselines";
+
+/// The baseline each day's previous results are copied to before a run
+/// replaces them.
+pub const LAST: &str = "last";
+
+/// The results file of `day` in the baseline `name`, e.g.
+/// `bench/results/rs/baselines/main/day_15.csv`.
+pub fn file(results_dir: &Path, name: &str, day: &str) -> PathBuf {
+    results_dir
+        .join(BASELINES_DIR)
+        .join(name)
+        .join(format!("{}.csv", day))
+}
+
+/// Keeps the results of `day`'s previous run, if there are any, as its
+/// [`LAST`] baseline.
+pub fn keep_last(results_dir: &Path, day: &str) -> io::Result<()> {
+    let results = results_dir.join(format!("{}.csv", day));
+    if !results.exists() {
+        return Ok(());
+    }
+    let last = file(results_dir, LAST, day);
+    create_dir_all(last.parent().unwrap())?;
+    copy(results, last).map(|_| ())
+}
+
+/// Reads each part's elapsed times from a results file.
+pub fn load(file: &Path) -> io::Result<HashMap<String, Vec<f64>>> {
+    let invalid = |line: &str| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: invalid result: {}", file.display(), line),
+        )
+    };
+
+    let mut parts = HashMap::<_, Vec<_>>::new();
+    for line in read_to_string(file)?.lines().skip(1) {
+        let mut fields = line.split(',');
+        let (part, elapsed) = match (fields.next(), fields.nth(1)) {
+            (Some(part), Some(elapsed)) => (part, elapsed),
+            _ => return Err(invalid(line)),
+        };
+        let elapsed = elapsed.parse().map_err(|_| invalid(line))?;
+        parts.entry(part.to_owned()).or_default().push(elapsed);
+    }
+    Ok(parts)
+}
+
+/// How a part's times changed from the baseline's.
+pub struct Comparison {
+    pub day: u8,
+    pub part: String,
+    /// The median times, in nanoseconds.
+    pub baseline: f64,
+    pub current: f64,
+    /// The p-value of the times being no different.
+    pub p: f64,
+}
+
+impl Comparison {
+    pub fn new(day: u8, part: String, baseline: &[f64], current: &[f64]) -> Option<Self> {
+        Some(Comparison {
+            day,
+            part,
+            baseline: Stats::new(baseline)?.median,
+            current: Stats::new(current)?.median,
+            p: mann_whitney(baseline, current),
+        })
+    }
+
+    /// The change in the median, in percent.
+    pub fn change(&self) -> f64 {
+        (self.current - self.baseline) / self.baseline * 100.0
+    }
+
+    /// Whether the change is significant at the level `alpha`.
+    pub fn significant(&self, alpha: f64) -> bool {
+        self.p < alpha
+    }
+
+    /// Whether it is significantly slower than the baseline, by more than
+    /// `threshold` percent.
+    pub fn regressed(&self, alpha: f64, threshold: f64) -> bool {
+        self.significant(alpha) && self.change() > threshold
+    }
+}
+
+/// A table of `comparisons`, with a verdict for each.
+pub fn report(comparisons: &[Comparison], alpha: f64, threshold: f64) -> String {
+    let mut out = format!(
+        "{:<5}{:<8}{:>14}{:>14}{:>10}{:>8}  Verdict\n",
+        "Day", "Part", "Baseline", "Current", "Change", "p"
+    );
+    for comparison in comparisons {
+        let verdict = match (
+            comparison.regressed(alpha, threshold),
+            comparison.significant(alpha),
+            comparison.change() < 0.0,
+        ) {
+            (true, ..) => "REGRESSED",
+            (false, true, true) => "faster",
+            (false, true, false) => "slower",
+            (false, false, _) => "no change",
+        };
+        let ns = |nanos: f64| {
+            format!(
+                "{:.0?}",
+                std::time::Duration::from_nanos(nanos.round() as u64)
+            )
+        };
+        writeln!(
+            out,
+            "{:<5}{:<8}{:>14}{:>14}{:>+9.2}%{:>8.3}  {}",
+            comparison.day,
+            comparison.part,
+            ns(comparison.baseline),
+            ns(comparison.current),
+            comparison.change(),
+            comparison.p,
+            verdict
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env,
+        fs::{remove_dir_all, remove_file, write},
+    };
+
+    #[test]
+    fn keep_last() {
+        let results_dir = env::temp_dir().join(format!("benchrs-last-{}", std::process::id()));
+        create_dir_all(&results_dir).unwrap();
+
+        super::keep_last(&results_dir, "day_1").unwrap();
+        let missing = file(&results_dir, LAST, "day_1").exists();
+        write(
+            results_dir.join("day_1.csv"),
+            "Part,Run,Elapsed (ns)
+read,1,120
+",
+        )
+        .unwrap();
+        super::keep_last(&results_dir, "day_1").unwrap();
+        let last = super::load(&file(&results_dir, LAST, "day_1")).unwrap();
+        remove_dir_all(&results_dir).unwrap();
+
+        assert!(!missing);
+        assert_eq!(Some(&vec![120.0]), last.get("read"));
+    }
+
+    #[test]
+    fn load() {
+        let file = env::temp_dir().join(format!("benchrs-baseline-{}.csv", std::process::id()));
+        write(
+            &file,
+            "Part,Run,Elapsed (ns),Largest allocation (B)\nread,1,120,0\npart 1,1,30,0\nread,2,100,0\n",
+        )
+        .unwrap();
+
+        let parts = super::load(&file).unwrap();
+        remove_file(&file).unwrap();
+
+        assert_eq!(Some(&vec![120.0, 100.0]), parts.get("read"));
+        assert_eq!(Some(&vec![30.0]), parts.get("part 1"));
+    }
+
+    #[test]
+    fn regressed() {
+        let baseline = (0..40)
+            .map(|i| 1000.0 + f64::from(i % 5))
+            .collect::<Vec<_>>();
+        let slower = baseline.iter().map(|x| x * 1.1).collect::<Vec<_>>();
+        let comparison = Comparison::new(15, "part 2".to_owned(), &baseline, &slower).unwrap();
+
+        assert!((comparison.change() - 10.0).abs() < 1e-9);
+        assert!(comparison.regressed(0.05, 5.0));
+        assert!(!comparison.regressed(0.05, 15.0));
+        assert!(report(&[comparison], 0.05, 5.0).contains("+10.00%"));
+    }
+}
@@ -1,68 +1,139 @@
-#[deny(clippy::all)]
+#![deny(clippy::all)]
+use aoc::Days;
+use baseline::Comparison;
+use clap::Parser;
 use regex::Regex;
+use solution::Answers;
+use stats::Stats;
 use std::alloc::{GlobalAlloc, Layout, System};
-use std::env;
+use std::cell::Cell;
 use std::fmt::{Debug, Display, Write};
 use std::io::{BufRead, BufReader};
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::{exit, Command, Stdio};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
 use std::time::{Duration, Instant};
 
+mod baseline;
+mod stats;
+
 macro_rules! run_day {
-    ($module:ident) => {
+    ($module:ident, $opts:expr) => {{
+        let opts: &Opts = $opts;
+        let budget = opts.budget();
+        let day = stringify!($module)
+            .trim_start_matches("day_")
+            .parse::<u8>()
+            .unwrap();
         println!("Running benchmarks for {}...", stringify!($module));
-        let mut result_file =
-            ResultFile::new(format!("./bench/results/rs/{}.csv", stringify!($module)).as_str())
-                .unwrap();
 
-        let results = run_bench(Parts::ReadData, || {
-            let data = $module::read_data("./data");
-            assert_ne!(data, $module::Input::default());
+        // The previous run is kept first, so that it can be compared with
+        // as the 'last' baseline.
+        baseline::keep_last(&opts.results_dir, stringify!($module)).unwrap();
+        // Loaded before saving, in case it is also the baseline being saved.
+        let baseline = opts.baseline.as_ref().and_then(|name| {
+            let file = baseline::file(&opts.results_dir, name, stringify!($module));
+            match baseline::load(&file) {
+                Ok(baseline) => Some(baseline),
+                Err(e) => {
+                    eprintln!("No baseline to compare against: {}: {}", file.display(), e);
+                    None
+                }
+            }
         });
-
-        result_file.write(&results).unwrap();
-
-        println!("Parse:\n\t{}\n", process_results(results));
-
-        let data = $module::read_data("./data");
-
-        let results = run_bench(Parts::One, || {
-            assert_eq!($module::part_1(&data), $module::PART_1);
+        let mut saved_file = opts.save_baseline.as_ref().map(|name| {
+            let file = baseline::file(&opts.results_dir, name, stringify!($module));
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            ResultFile::new(&file).unwrap()
         });
+        let mut result_file = ResultFile::new(
+            &opts
+                .results_dir
+                .join(format!("{}.csv", stringify!($module))),
+        )
+        .unwrap();
+        let mut summary_file = SummaryFile::new(
+            &opts
+                .results_dir
+                .join(format!("{}_summary.csv", stringify!($module))),
+        )
+        .unwrap();
 
-        result_file.write(&results).unwrap();
+        let mut comparisons = Vec::new();
+        let mut record = |results: BenchResult, label: &str| {
+            result_file.write(&results).unwrap();
+            summary_file.write(&results).unwrap();
+            if let Some(file) = &mut saved_file {
+                file.write(&results).unwrap();
+            }
 
-        println!("Part 1:\n\t{}\n", process_results(results));
+            println!("{}:\n{}\n", label, process_results(&results));
 
-        let results = run_bench(Parts::Two, || {
-            assert_eq!($module::part_2(&data), $module::PART_2);
-        });
+            let part = results.part.to_string();
+            if let Some(baseline) = baseline.as_ref().and_then(|baseline| baseline.get(&part)) {
+                let current = results
+                    .results
+                    .iter()
+                    .map(|sample| sample.elapsed as f64)
+                    .collect::<Vec<_>>();
+                comparisons.extend(Comparison::new(day, part, baseline, &current));
+            }
+        };
 
-        result_file.write(&results).unwrap();
+        if opts.includes(Parts::ReadData) {
+            let results = run_bench(Parts::ReadData, &budget, || {
+                #[cfg(feature = "embed-input")]
+                let data = $module::parse_embedded().unwrap();
+                #[cfg(not(feature = "embed-input"))]
+                let data = $module::read_data("./data").unwrap();
+                assert_ne!(data, $module::Input::default());
+            });
+            record(results, "Parse");
+        }
 
-        println!("Part 2:\n\t{}\n", process_results(results));
+        let data = $module::read_data("./data").unwrap();
+        let expected = Answers::expected("./data", day).unwrap();
 
-        let results = run_bench(Parts::Total, || {
-            let data = $module::read_data("./data");
-            assert_ne!(data, $module::Input::default());
-            assert_eq!($module::part_1(&data), $module::PART_1);
-            assert_eq!($module::part_2(&data), $module::PART_2);
-        });
+        if opts.includes(Parts::One) {
+            let results = run_bench(Parts::One, &budget, || {
+                assert_eq!($module::part_1(&data), expected.part_1);
+            });
+            record(results, "Part 1");
+        }
 
-        result_file.write(&results).unwrap();
+        if opts.includes(Parts::Two) {
+            let results = run_bench(Parts::Two, &budget, || {
+                assert_eq!($module::part_2(&data), expected.part_2);
+            });
+            record(results, "Part 2");
+        }
 
-        println!("Total:\n\t{}\n", process_results(results));
+        if opts.includes(Parts::Total) {
+            let results = run_bench(Parts::Total, &budget, || {
+                let data = $module::read_data("./data").unwrap();
+                assert_ne!(data, $module::Input::default());
+                assert_eq!($module::part_1(&data), expected.part_1);
+                assert_eq!($module::part_2(&data), expected.part_2);
+            });
+            record(results, "Total");
+        }
 
         // Perform a benchmark run using the built-in Cargo benchmark
         // to provide a baseline for the impact of the custom counting
         // memory allocator
         // Writes the shown median, plus derived min. and max. times for each part
         // to an output CSV.
-        println!("Running 'cargo bench {}'...", stringify!($module));
-        cargo_bench(stringify!($module));
-    };
+        if !opts.no_cargo_bench {
+            println!("Running 'cargo bench {}'...", stringify!($module));
+            cargo_bench(stringify!($module), &opts.results_dir);
+        }
+
+        comparisons
+    }};
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub(crate) enum Parts {
     ReadData,
     One,
@@ -81,122 +152,253 @@ impl Display for Parts {
     }
 }
 
+impl FromStr for Parts {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Parts::ReadData),
+            "1" => Ok(Parts::One),
+            "2" => Ok(Parts::Two),
+            "total" => Ok(Parts::Total),
+            _ => Err(format!("invalid part: {}", s)),
+        }
+    }
+}
+
+#[derive(clap::Parser, Debug)]
+struct Opts {
+    #[clap(
+        value_name = "DAYS",
+        default_value = "all",
+        help = "Days to benchmark: 'all', a single day, a range such as '3-7', or a comma-separated list of these"
+    )]
+    days: Days,
+
+    #[clap(
+        short,
+        long = "part",
+        value_name = "PARTS",
+        use_delimiter = true,
+        possible_values = ["read", "1", "2", "total"],
+        help = "Only benchmark the given parts, e.g. '1,2' [default: all of them]"
+    )]
+    parts: Vec<Parts>,
+
+    #[clap(
+        long,
+        value_name = "DURATION",
+        parse(try_from_str = parse_duration),
+        help = "Keep sampling a part until it has run for this long, e.g. '30s' or '500ms', once it has \
+                the minimum number of samples [default: 30s]"
+    )]
+    runtime_limit: Option<Duration>,
+
+    #[clap(
+        long,
+        value_name = "DURATION",
+        parse(try_from_str = parse_duration),
+        help = "Repeat a part within each sample until the sample takes at least this long, so that \
+                quick parts are timed accurately [default: 1ms]"
+    )]
+    runtime_target: Option<Duration>,
+
+    #[clap(
+        long,
+        value_name = "RUNS",
+        help = "Most samples to take of a part, once it has the minimum [default: 10240]"
+    )]
+    run_limit: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "RUNS",
+        help = "Fewest samples to take of a part, however long they take [default: 100]"
+    )]
+    min_runs: Option<usize>,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        default_value = "./bench/results/rs",
+        help = "Directory to write each day's results CSVs to"
+    )]
+    results_dir: PathBuf,
+
+    #[clap(
+        long,
+        help = "Skip the 'cargo bench' run that follows each day, giving a baseline without the \
+                counting allocator"
+    )]
+    no_cargo_bench: bool,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "Also save the results as the baseline NAME, in '<DIR>/baselines/NAME/', replacing the \
+                days benchmarked"
+    )]
+    save_baseline: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "Compare each part's times with those of the baseline NAME, reporting the change in the \
+                median and whether it is significant. 'last' is the previous run of each day"
+    )]
+    baseline: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        default_value = "5",
+        help = "Exit with status 2 if a part is significantly slower than the baseline by more than \
+                this"
+    )]
+    threshold: f64,
+
+    #[clap(
+        long,
+        value_name = "P",
+        default_value = "0.05",
+        help = "p-value below which a change from the baseline is significant"
+    )]
+    significance: f64,
+}
+
+impl Opts {
+    fn includes(&self, part: Parts) -> bool {
+        self.parts.is_empty() || self.parts.contains(&part)
+    }
+
+    fn budget(&self) -> Budget {
+        Budget {
+            runtime_limit: self.runtime_limit.unwrap_or(RUNTIME_LIMIT),
+            runtime_target: self.runtime_target.unwrap_or(RUNTIME_TARGET),
+            run_limit: self.run_limit.unwrap_or(RUN_LIMIT),
+            min_runs: self.min_runs.unwrap_or(MIN_RUNS),
+        }
+    }
+}
+
 fn main() {
-    if env::args().len() > 1 {
-        match env::args().nth(1).unwrap().parse::<usize>().unwrap() {
-            1 => {
-                run_day!(day_1);
-            }
-            2 => {
-                run_day!(day_2);
+    let opts = Opts::parse();
+    std::fs::create_dir_all(&opts.results_dir).unwrap();
+
+    let mut missing = Vec::new();
+    let mut comparisons = Vec::new();
+    for day in 1..=25 {
+        if opts.days.contains(day) {
+            match run(day, &opts) {
+                Some(compared) => comparisons.extend(compared),
+                None => missing.push(day),
             }
-            3 => {
-                run_day!(day_3);
-            }
-            4 => {
-                run_day!(day_4);
-            }
-            5 => {
-                run_day!(day_5);
-            }
-            6 => {
-                run_day!(day_6);
-            }
-            7 => {
-                run_day!(day_7);
-            }
-            8 => {
-                run_day!(day_8);
-            }
-            9 => {
-                run_day!(day_9);
-            }
-            10 => {
-                run_day!(day_10);
-            }
-            11 => {
-                run_day!(day_11);
-            }
-            12 => {
-                run_day!(day_12);
-            }
-            13 => {
-                run_day!(day_13);
-            }
-            14 => {
-                run_day!(day_14);
-            }
-            15 => {
-                run_day!(day_15);
-            }
-            16 => {
-                run_day!(day_16);
-            }
-            17 => {
-                run_day!(day_17);
-            }
-            // 18 => {run_day!(day_18);},
-            19 => {
-                run_day!(day_19);
-            }
-            20 => {
-                run_day!(day_20);
-            }
-            21 => {
-                run_day!(day_21);
-            }
-            // 22 => {run_day!(day_22);},
-            // 23 => {run_day!(day_23);},
-            // 24 => {run_day!(day_24);},
-            25 => {
-                run_day!(day_25);
-            }
-            _ => {}
         }
-        return;
-    };
+    }
 
-    run_day!(day_1);
-    run_day!(day_2);
-    run_day!(day_3);
-    run_day!(day_4);
-    run_day!(day_5);
-    run_day!(day_6);
-    run_day!(day_7);
-    run_day!(day_8);
-    run_day!(day_9);
-    run_day!(day_10);
-    run_day!(day_11);
-    run_day!(day_12);
-    run_day!(day_13);
-    run_day!(day_14);
-    run_day!(day_15);
-    run_day!(day_16);
-    run_day!(day_17);
-    // run_day!(day_18);
-    run_day!(day_19);
-    run_day!(day_20);
-    run_day!(day_21);
-    // run_day!(day_22);
-    // run_day!(day_23);
-    // run_day!(day_24);
-    run_day!(day_25);
+    let mut regressed = false;
+    if let Some(name) = &opts.baseline {
+        println!(
+            "Compared with baseline '{}':\n{}",
+            name,
+            baseline::report(&comparisons, opts.significance, opts.threshold)
+        );
+        regressed = comparisons
+            .iter()
+            .any(|comparison| comparison.regressed(opts.significance, opts.threshold));
+    }
+
+    // Days without a solution are only worth mentioning if asked for.
+    if opts.days != Days::All && !missing.is_empty() {
+        eprintln!(
+            "No benchmarks for day(s) {}",
+            missing
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        exit(1)
+    }
+    if regressed {
+        eprintln!("Slower than the baseline by more than {}%", opts.threshold);
+        exit(2)
+    }
+}
+
+/// Benchmarks a single day, returning how it compares with the baseline, or
+/// `None` if it has not been implemented.
+fn run(day: u8, opts: &Opts) -> Option<Vec<Comparison>> {
+    let comparisons = match day {
+        1 => run_day!(day_1, opts),
+        2 => run_day!(day_2, opts),
+        3 => run_day!(day_3, opts),
+        4 => run_day!(day_4, opts),
+        5 => run_day!(day_5, opts),
+        6 => run_day!(day_6, opts),
+        7 => run_day!(day_7, opts),
+        8 => run_day!(day_8, opts),
+        9 => run_day!(day_9, opts),
+        10 => run_day!(day_10, opts),
+        11 => run_day!(day_11, opts),
+        12 => run_day!(day_12, opts),
+        13 => run_day!(day_13, opts),
+        14 => run_day!(day_14, opts),
+        15 => run_day!(day_15, opts),
+        16 => run_day!(day_16, opts),
+        17 => run_day!(day_17, opts),
+        // 18 => run_day!(day_18, opts),
+        19 => run_day!(day_19, opts),
+        20 => run_day!(day_20, opts),
+        21 => run_day!(day_21, opts),
+        22 => run_day!(day_22, opts),
+        // 23 => run_day!(day_23, opts),
+        // 24 => run_day!(day_24, opts),
+        25 => run_day!(day_25, opts),
+        _ => return None,
+    };
+    Some(comparisons)
 }
 
 pub struct Counter;
 
+/// Bytes currently allocated.
 static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// The most bytes allocated at once since the last reset.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated since the last reset, including growth by reallocation.
+static TOTAL_ALLOC: AtomicUsize = AtomicUsize::new(0);
+/// The largest single allocation since the last reset.
 static MAX_ALLOC: AtomicUsize = AtomicUsize::new(0);
 static NUM_ALLOC: AtomicUsize = AtomicUsize::new(0);
+static NUM_REALLOC: AtomicUsize = AtomicUsize::new(0);
+/// Threads other than the benchmarking one that allocated, counted once per
+/// run they allocated in. Threads that never allocate can't be seen.
+static ALLOCATING_THREADS: AtomicUsize = AtomicUsize::new(0);
+/// Bytes and allocations on those threads.
+static OTHER_ALLOC: AtomicUsize = AtomicUsize::new(0);
+static OTHER_NUM_ALLOC: AtomicUsize = AtomicUsize::new(0);
+/// Bumped for every run, so each thread is counted once per run.
+static RUN: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    /// Whether this is the benchmarking thread, and the last run this thread
+    /// was counted in.
+    static THREAD: Cell<(bool, usize)> = const { Cell::new((false, 0)) };
+}
 
 unsafe impl GlobalAlloc for Counter {
     #[inline]
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         let ret = System.alloc(layout);
         if !ret.is_null() {
-            ALLOCATED.fetch_add(layout.size(), SeqCst);
+            Counter::grow(layout.size());
             MAX_ALLOC.fetch_max(layout.size(), SeqCst);
             NUM_ALLOC.fetch_add(1, SeqCst);
+            if Counter::other_thread() {
+                OTHER_ALLOC.fetch_add(layout.size(), SeqCst);
+                OTHER_NUM_ALLOC.fetch_add(1, SeqCst);
+            }
         }
         ret
     }
@@ -206,20 +408,104 @@ unsafe impl GlobalAlloc for Counter {
         System.dealloc(ptr, layout);
         ALLOCATED.fetch_sub(layout.size(), SeqCst);
     }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let ret = System.realloc(ptr, layout, new_size);
+        if !ret.is_null() {
+            match new_size.checked_sub(layout.size()) {
+                Some(growth) => {
+                    Counter::grow(growth);
+                    if Counter::other_thread() {
+                        OTHER_ALLOC.fetch_add(growth, SeqCst);
+                    }
+                }
+                None => {
+                    ALLOCATED.fetch_sub(layout.size() - new_size, SeqCst);
+                }
+            }
+            MAX_ALLOC.fetch_max(new_size, SeqCst);
+            NUM_REALLOC.fetch_add(1, SeqCst);
+        }
+        ret
+    }
 }
 
 impl Counter {
     fn reset() {
+        PEAK.store(ALLOCATED.load(SeqCst), SeqCst);
+        TOTAL_ALLOC.store(0, SeqCst);
         MAX_ALLOC.store(0, SeqCst);
         NUM_ALLOC.store(0, SeqCst);
+        NUM_REALLOC.store(0, SeqCst);
+        ALLOCATING_THREADS.store(0, SeqCst);
+        OTHER_ALLOC.store(0, SeqCst);
+        OTHER_NUM_ALLOC.store(0, SeqCst);
+    }
+
+    /// Marks the calling thread as the one running the benchmarks, whose
+    /// allocations aren't attributed to other threads.
+    fn bench_thread() {
+        THREAD.with(|thread| thread.set((true, 0)));
+    }
+
+    /// Starts a new run, in which each thread is counted again.
+    #[inline]
+    fn next_run() {
+        RUN.fetch_add(1, SeqCst);
+    }
+
+    /// Whether the allocating thread is another than the benchmarking one,
+    /// counting it if it is the first time it has allocated this run.
+    #[inline]
+    fn other_thread() -> bool {
+        // Threads being torn down can no longer tell, but aren't the
+        // benchmarking one.
+        THREAD
+            .try_with(|thread| {
+                let (bench, counted) = thread.get();
+                let run = RUN.load(SeqCst);
+                if !bench && counted != run {
+                    thread.set((false, run));
+                    ALLOCATING_THREADS.fetch_add(1, SeqCst);
+                }
+                !bench
+            })
+            .unwrap_or(true)
+    }
+
+    #[inline]
+    fn grow(size: usize) {
+        let allocated = ALLOCATED.fetch_add(size, SeqCst) + size;
+        PEAK.fetch_max(allocated, SeqCst);
+        TOTAL_ALLOC.fetch_add(size, SeqCst);
     }
 }
 
 #[global_allocator]
 pub static mut A: Counter = Counter;
 
+/// A single run of a part, or the average run of a sample of quick parts.
+struct Sample {
+    elapsed: u64,
+    /// The most bytes in use at once, beyond those in use beforehand.
+    peak_memory: usize,
+    total_alloc: usize,
+    largest_alloc: usize,
+    num_alloc: usize,
+    num_realloc: usize,
+    /// CPU time, across every thread.
+    user_time: u64,
+    system_time: u64,
+    /// Other threads that allocated memory, and their share of the
+    /// allocations above.
+    allocating_threads: usize,
+    other_alloc: usize,
+    other_num_alloc: usize,
+}
+
 struct BenchResult {
-    results: Vec<(u64, usize, usize)>,
+    results: Vec<Sample>,
     part: Parts,
 }
 
@@ -231,35 +517,41 @@ impl BenchResult {
         }
     }
 
-    fn add(&mut self, elapsed: u64, max_alloc: usize, num_alloc: usize) {
-        self.results.push((elapsed, max_alloc, num_alloc));
+    fn add(&mut self, sample: Sample) {
+        self.results.push(sample);
     }
 
-    fn means(&self) -> (Duration, Bytes, usize) {
-        let means = self
-            .results
-            .iter()
-            .fold((0u64, 0usize, 0usize), |acc, result| {
-                (acc.0 + result.0, acc.1 + result.1, acc.2 + result.2)
-            });
-        (
-            Duration::from_nanos(means.0 / self.results.len() as u64),
-            Bytes(means.1 / self.results.len()),
-            means.2 / self.results.len(),
+    /// Summarises one of the figures recorded for each sample.
+    fn stats(&self, figure: impl Fn(&Sample) -> usize) -> Stats {
+        Stats::new(
+            &self
+                .results
+                .iter()
+                .map(|sample| figure(sample) as f64)
+                .collect::<Vec<_>>(),
         )
+        .unwrap()
     }
 
     fn to_csv(&self) -> String {
         let mut out = String::with_capacity(128 * self.results.len());
 
-        for (run, result) in self.results.iter().enumerate().take(self.results.len()) {
+        for (run, sample) in self.results.iter().enumerate() {
             out.write_fmt(format_args!(
-                "{},{},{},{},{}\n",
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
                 self.part,
                 run + 1,
-                result.0,
-                result.1,
-                result.2
+                sample.elapsed,
+                sample.largest_alloc,
+                sample.num_alloc,
+                sample.peak_memory,
+                sample.total_alloc,
+                sample.num_realloc,
+                sample.user_time,
+                sample.system_time,
+                sample.allocating_threads,
+                sample.other_alloc,
+                sample.other_num_alloc,
             ))
             .unwrap();
         }
@@ -273,7 +565,7 @@ struct ResultFile {
 }
 
 impl ResultFile {
-    fn new(filepath: &str) -> std::io::Result<Self> {
+    fn new(filepath: &Path) -> std::io::Result<Self> {
         let mut file = std::fs::File::options()
             .truncate(true)
             .write(true)
@@ -282,7 +574,9 @@ impl ResultFile {
 
         std::io::Write::write_all(
             &mut file,
-            b"Part,Run,Elapsed (ns),Max. memory (B), Num. allocations\n",
+            b"Part,Run,Elapsed (ns),Largest allocation (B),Num. allocations,Peak memory (B),\
+              Total allocated (B),Num. reallocations,User time (ns),System time (ns),Allocating threads,\
+              Other threads' allocated (B),Other threads' num. allocations\n",
         )?;
 
         Ok(Self { file })
@@ -293,67 +587,193 @@ impl ResultFile {
     }
 }
 
+/// One row per part of the statistics [`process_results`] prints.
+struct SummaryFile {
+    file: std::fs::File,
+}
+
+impl SummaryFile {
+    fn new(filepath: &Path) -> std::io::Result<Self> {
+        let mut file = std::fs::File::create(filepath)?;
+
+        std::io::Write::write_all(
+            &mut file,
+            b"Part,Runs,Median (ns),Min (ns),Max (ns),P5 (ns),P95 (ns),P99 (ns),Std. dev. (ns),MAD (ns),\
+              Low severe outliers,Low mild outliers,High mild outliers,High severe outliers,\
+              Median peak memory (B),Median total allocated (B),Median largest allocation (B),\
+              Median num. allocations,Median num. reallocations,Median user time (ns),\
+              Median system time (ns),Median allocating threads,Median other threads' allocated (B),\
+              Median other threads' num. allocations\n",
+        )?;
+
+        Ok(Self { file })
+    }
+
+    fn write(&mut self, results: &BenchResult) -> std::io::Result<()> {
+        let time = results.stats(|sample| sample.elapsed as usize);
+        let median = |figure: fn(&Sample) -> usize| results.stats(figure).median;
+        let row = format!(
+            "{},{},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{},{},{},{},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0},{:.0}\n",
+            results.part,
+            results.results.len(),
+            time.median,
+            time.min,
+            time.max,
+            time.p5,
+            time.p95,
+            time.p99,
+            time.std_dev,
+            time.mad,
+            time.outliers.low_severe,
+            time.outliers.low_mild,
+            time.outliers.high_mild,
+            time.outliers.high_severe,
+            median(|sample| sample.peak_memory),
+            median(|sample| sample.total_alloc),
+            median(|sample| sample.largest_alloc),
+            median(|sample| sample.num_alloc),
+            median(|sample| sample.num_realloc),
+            median(|sample| sample.user_time as usize),
+            median(|sample| sample.system_time as usize),
+            median(|sample| sample.allocating_threads),
+            median(|sample| sample.other_alloc),
+            median(|sample| sample.other_num_alloc),
+        );
+        std::io::Write::write_all(&mut self.file, row.as_bytes())
+    }
+}
+
 const RUNTIME_LIMIT: Duration = Duration::from_secs(30);
 const RUNTIME_TARGET: Duration = Duration::from_millis(1);
 const RUN_LIMIT: usize = 10240;
 const MIN_RUNS: usize = 100;
 
-fn run_bench<F: Fn()>(part: Parts, closure_to_time: F) -> BenchResult {
+/// How long, and how many times, to sample each part.
+struct Budget {
+    runtime_limit: Duration,
+    runtime_target: Duration,
+    run_limit: usize,
+    min_runs: usize,
+}
+
+fn run_bench<F: Fn()>(part: Parts, budget: &Budget, closure_to_time: F) -> BenchResult {
     let mut out = BenchResult::new(part);
 
+    Counter::bench_thread();
     Counter::reset();
     let mut total_time = Duration::default();
     let mut total_runs = 0;
 
     let mut n = 1;
-    while (total_runs < MIN_RUNS) || (total_time < RUNTIME_LIMIT && total_runs < RUN_LIMIT) {
+    while (total_runs < budget.min_runs)
+        || (total_time < budget.runtime_limit && total_runs < budget.run_limit)
+    {
         total_runs += 1;
         let mut start: Instant;
         let mut elapsed: Duration;
         let mut baseline: usize;
-        let mut num_alloc: usize;
-        let mut max_alloc: usize;
+        let mut cpu_time: (Duration, Duration);
         loop {
             Counter::reset();
             baseline = ALLOCATED.load(SeqCst);
+            let cpu_start = cpu_time_used();
             start = Instant::now();
             for _ in 0..n {
+                Counter::next_run();
                 closure_to_time();
             }
             elapsed = start.elapsed();
-            num_alloc = NUM_ALLOC.load(SeqCst);
-            max_alloc = MAX_ALLOC.load(SeqCst);
-            if elapsed >= RUNTIME_TARGET {
+            let cpu_end = cpu_time_used();
+            cpu_time = (cpu_end.0 - cpu_start.0, cpu_end.1 - cpu_start.1);
+            if elapsed >= budget.runtime_target {
                 break;
             }
-            n *= (RUNTIME_TARGET.as_nanos() / elapsed.as_nanos()).max(2) as usize;
+            n *= (budget.runtime_target.as_nanos() / elapsed.as_nanos()).max(2) as usize;
         }
 
         total_time += elapsed;
-        out.add(
+        // Totals are averaged over the runs in the sample. Each run frees what
+        // it allocates, so the peak and largest allocation are those of one.
+        out.add(Sample {
             // Counter alloc is 11ns slower per allocation than the system
             // allocator, so adjust the results to remove this additional latency
             // to avoid unfairly penalising functions that allocate lots of memory
-            elapsed.as_nanos() as u64 / n as u64,
-            if max_alloc > baseline {
-                max_alloc - baseline
-            } else {
-                max_alloc
-            },
-            num_alloc / n,
-        );
+            elapsed: elapsed.as_nanos() as u64 / n as u64,
+            peak_memory: PEAK.load(SeqCst).saturating_sub(baseline),
+            total_alloc: TOTAL_ALLOC.load(SeqCst) / n,
+            largest_alloc: MAX_ALLOC.load(SeqCst),
+            num_alloc: NUM_ALLOC.load(SeqCst) / n,
+            num_realloc: NUM_REALLOC.load(SeqCst) / n,
+            user_time: cpu_time.0.as_nanos() as u64 / n as u64,
+            system_time: cpu_time.1.as_nanos() as u64 / n as u64,
+            allocating_threads: ALLOCATING_THREADS.load(SeqCst) / n,
+            other_alloc: OTHER_ALLOC.load(SeqCst) / n,
+            other_num_alloc: OTHER_NUM_ALLOC.load(SeqCst) / n,
+        });
     }
     Counter::reset();
 
     out
 }
 
-fn process_results(results: BenchResult) -> String {
-    let (time, max_mem, num_mem) = results.means();
+/// The user and system CPU time used by the process so far, across all of its
+/// threads.
+#[cfg(unix)]
+fn cpu_time_used() -> (Duration, Duration) {
+    let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+    // SAFETY: getrusage only writes to the struct it is given.
+    let usage = unsafe {
+        assert_eq!(0, libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()));
+        usage.assume_init()
+    };
+    let duration =
+        |time: libc::timeval| Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1_000);
+    (duration(usage.ru_utime), duration(usage.ru_stime))
+}
+
+#[cfg(not(unix))]
+fn cpu_time_used() -> (Duration, Duration) {
+    (Duration::ZERO, Duration::ZERO)
+}
+
+fn process_results(results: &BenchResult) -> String {
+    let time = results.stats(|sample| sample.elapsed as usize);
+    let user_time = results.stats(|sample| sample.user_time as usize);
+    let system_time = results.stats(|sample| sample.system_time as usize);
+    let cpu_time = results.stats(|sample| (sample.user_time + sample.system_time) as usize);
+    let median = |figure: fn(&Sample) -> usize| results.stats(figure).median.round() as usize;
+    let ns = |nanos: f64| Duration::from_nanos(nanos.round() as u64);
 
     format!(
-        "Took: {:#?}\tMax memory: {:?}\t# allocations: {}",
-        time, max_mem, num_mem,
+        "\tTook: {:#?} (median)\t[{:#?} .. {:#?}]\tp5: {:#?}\tp95: {:#?}\tp99: {:#?}\n\
+         \tStd. dev.: {:#?}\tMAD: {:#?}\tOutliers: {} of {} ({} severe)\n\
+         \tPeak memory: {:?}\tTotal allocated: {:?}\tLargest allocation: {:?}\n\
+         \t# allocations: {}\t# reallocations: {}\n\
+         \tCPU time: {:#?} ({:#?} user, {:#?} system), {:.2}x the time taken\n\
+         \tAllocating threads: {}\tOn other threads: {:?} in {} allocations",
+        ns(time.median),
+        ns(time.min),
+        ns(time.max),
+        ns(time.p5),
+        ns(time.p95),
+        ns(time.p99),
+        ns(time.std_dev),
+        ns(time.mad),
+        time.outliers.total(),
+        results.results.len(),
+        time.outliers.severe(),
+        Bytes(median(|sample| sample.peak_memory)),
+        Bytes(median(|sample| sample.total_alloc)),
+        Bytes(median(|sample| sample.largest_alloc)),
+        median(|sample| sample.num_alloc),
+        median(|sample| sample.num_realloc),
+        ns(cpu_time.median),
+        ns(user_time.median),
+        ns(system_time.median),
+        cpu_time.median / time.median,
+        median(|sample| sample.allocating_threads),
+        Bytes(median(|sample| sample.other_alloc)),
+        median(|sample| sample.other_num_alloc),
     )
 }
 
@@ -385,99 +805,147 @@ impl Debug for Bytes {
     }
 }
 
-fn cargo_bench(day: &str) {
+fn cargo_bench(day: &str, results_dir: &Path) {
     let mut cargo_result_file =
-        ResultFile::new(format!("./bench/results/rs/{}_cargo.csv", day).as_str()).unwrap();
+        ResultFile::new(&results_dir.join(format!("{}_cargo.csv", day))).unwrap();
 
     let mut cmd = Command::new("cargo");
-    let bench = cmd
-        .args(["bench", "-p", day])
+    let mut bench = cmd
+        .args(["bench", "-p", day, "--bench", day])
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+        .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    let stdout_reader = BufReader::new(bench.stdout.unwrap());
+    let stdout_reader = BufReader::new(bench.stdout.take().unwrap());
+
+    // Criterion reports the lower bound, estimate and upper bound of each
+    // benchmark's time, e.g. `part_1  time:   [1.0402 µs 1.0431 µs 1.0463 µs]`.
+    let time_regex =
+        Regex::new(r"^([_\w]+)\s+time:\s+\[([\d.]+) (\S+) ([\d.]+) (\S+) ([\d.]+) (\S+)\]")
+            .unwrap();
 
     stdout_reader
         .lines()
-        .filter_map(|line| line.ok())
+        .map_while(Result::ok)
         .for_each(|line| {
             println!("{}", line);
-            if !line.contains(" bench: ") {
-                return;
-            }
-            Regex::new(r".*?tests::([_\w]+):.*bench:\s+([\d,]+).*\(\+/- ([\d,]+)")
-                .unwrap()
-                .captures_iter(&line)
-                .for_each(|c| {
-                    let part = match c.get(1).unwrap().as_str() {
-                        "read_data" => Parts::ReadData,
-                        "part_1" => Parts::One,
-                        "part_2" => Parts::Two,
-                        "total" => Parts::Total,
-                        _ => return,
-                    };
-                    let median = Duration::from_nanos(
-                        c.get(2)
-                            .unwrap()
-                            .as_str()
-                            .chars()
-                            .filter_map(|c| {
-                                if c.is_numeric() {
-                                    Some(c as u8 - b'0')
-                                } else {
-                                    None
-                                }
-                            })
-                            .fold(0u64, |acc, n| (acc * 10) + n as u64),
-                    );
-                    let width = Duration::from_nanos(
-                        c.get(3)
-                            .unwrap()
-                            .as_str()
-                            .chars()
-                            .filter_map(|c| {
-                                if c.is_numeric() {
-                                    Some(c as u8 - b'0')
-                                } else {
-                                    None
-                                }
-                            })
-                            .fold(0u64, |acc, n| (acc * 10) + n as u64),
-                    );
-
-                    std::io::Write::write_all(
-                        &mut cargo_result_file.file,
-                        format!("{},{},{},{},{}\n", part, 1, median.as_nanos(), 0, 0,).as_bytes(),
-                    )
-                    .unwrap();
-                    std::io::Write::write_all(
-                        &mut cargo_result_file.file,
-                        format!(
-                            "{},{},{},{},{}\n",
-                            part,
-                            2,
-                            (median - (width / 2)).as_nanos(),
-                            0,
-                            0,
-                        )
-                        .as_bytes(),
+            time_regex.captures_iter(&line).for_each(|c| {
+                let part = match c.get(1).unwrap().as_str() {
+                    "read_data" => Parts::ReadData,
+                    "part_1" => Parts::One,
+                    "part_2" => Parts::Two,
+                    "total" => Parts::Total,
+                    _ => return,
+                };
+                let time = |idx| {
+                    criterion_duration(
+                        c.get(idx).unwrap().as_str(),
+                        c.get(idx + 1).unwrap().as_str(),
                     )
-                    .unwrap();
+                };
+                let (lower, estimate, upper) = (time(2), time(4), time(6));
+
+                for (row, duration) in [(1, estimate), (2, lower), (3, upper)] {
                     std::io::Write::write_all(
                         &mut cargo_result_file.file,
                         format!(
-                            "{},{},{},{},{}\n",
+                            "{},{},{},0,0,0,0,0,0,0,0,0,0\n",
                             part,
-                            3,
-                            (median + ((width + Duration::from_nanos(1)) / 2)).as_nanos(),
-                            0,
-                            0,
+                            row,
+                            duration.as_nanos()
                         )
                         .as_bytes(),
                     )
                     .unwrap();
-                });
+                }
+            });
         });
+
+    bench.wait().unwrap();
+}
+
+/// Converts a time printed by criterion, such as `1.0431 µs`, to a duration.
+fn criterion_duration(value: &str, unit: &str) -> Duration {
+    to_duration(value.parse().unwrap(), unit)
+        .unwrap_or_else(|| panic!("unknown time unit: {}", unit))
+}
+
+/// Parses a duration given on the command line, such as `30s` or `1.5ms`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let unit = s.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let value = s[..s.len() - unit.len()]
+        .parse()
+        .map_err(|_| format!("invalid duration: {}", s))?;
+    to_duration(value, unit.trim()).ok_or_else(|| {
+        format!(
+            "invalid duration: {}, expected a unit of ns, us, ms, s or m",
+            s
+        )
+    })
+}
+
+fn to_duration(value: f64, unit: &str) -> Option<Duration> {
+    let nanos = match unit {
+        "ps" => value / 1_000.0,
+        "ns" => value,
+        "µs" | "us" => value * 1_000.0,
+        "ms" => value * 1_000_000.0,
+        "s" => value * 1_000_000_000.0,
+        "m" => value * 60_000_000_000.0,
+        _ => return None,
+    };
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod parse_duration {
+        use super::*;
+
+        struct Case<'c> {
+            input: &'c str,
+            expected: Result<Duration, String>,
+        }
+
+        #[test]
+        fn seconds() {
+            run(&Case {
+                input: "30s",
+                expected: Ok(Duration::from_secs(30)),
+            })
+        }
+
+        #[test]
+        fn fractional() {
+            run(&Case {
+                input: "1.5ms",
+                expected: Ok(Duration::from_micros(1500)),
+            })
+        }
+
+        #[test]
+        fn no_unit() {
+            run(&Case {
+                input: "30",
+                expected: Err(
+                    "invalid duration: 30, expected a unit of ns, us, ms, s or m".to_string(),
+                ),
+            })
+        }
+
+        #[test]
+        fn no_value() {
+            run(&Case {
+                input: "ms",
+                expected: Err("invalid duration: ms".to_string()),
+            })
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, parse_duration(test.input))
+        }
+    }
 }
@@ -0,0 +1,253 @@
+//! Summary statistics of a part's samples, robust to the odd noisy sample on a
+//! shared machine.
+
+/// Summarises a set of samples.
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p5: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub std_dev: f64,
+    /// The median absolute deviation from the median.
+    pub mad: f64,
+    pub outliers: Outliers,
+}
+
+/// Samples outside Tukey's fences: beyond 1.5 (mild) or 3 (severe) times the
+/// interquartile range below the first or above the third quartile.
+#[derive(Debug, PartialEq, Default)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+impl Stats {
+    /// Summarises `samples`, or returns `None` if there are none.
+    pub fn new(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let median = percentile(&sorted, 50.0);
+        let mean = sorted.iter().sum::<f64>() / sorted.len() as f64;
+        let variance = match sorted.len() {
+            1 => 0.0,
+            n => sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64,
+        };
+
+        let mut deviations = sorted
+            .iter()
+            .map(|x| (x - median).abs())
+            .collect::<Vec<_>>();
+        deviations.sort_by(f64::total_cmp);
+
+        Some(Stats {
+            median,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            p5: percentile(&sorted, 5.0),
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+            std_dev: variance.sqrt(),
+            mad: percentile(&deviations, 50.0),
+            outliers: Outliers::new(&sorted),
+        })
+    }
+}
+
+impl Outliers {
+    fn new(sorted: &[f64]) -> Self {
+        let (q1, q3) = (percentile(sorted, 25.0), percentile(sorted, 75.0));
+        let iqr = q3 - q1;
+        let fence = |k: f64| (q1 - k * iqr, q3 + k * iqr);
+        let ((mild_low, mild_high), (severe_low, severe_high)) = (fence(1.5), fence(3.0));
+
+        let mut outliers = Outliers::default();
+        for &x in sorted {
+            if x < severe_low {
+                outliers.low_severe += 1;
+            } else if x < mild_low {
+                outliers.low_mild += 1;
+            } else if x > severe_high {
+                outliers.high_severe += 1;
+            } else if x > mild_high {
+                outliers.high_mild += 1;
+            }
+        }
+        outliers
+    }
+
+    pub fn total(&self) -> usize {
+        self.low_severe + self.low_mild + self.high_mild + self.high_severe
+    }
+
+    pub fn severe(&self) -> usize {
+        self.low_severe + self.high_severe
+    }
+}
+
+/// The `p`th percentile of `sorted`, interpolating between the samples either
+/// side of it.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// The two-sided p-value of a Mann-Whitney U test that samples `a` and `b`
+/// come from the same distribution. Unlike a t-test, it doesn't assume that
+/// times are normally distributed, and a few outliers barely move it.
+pub fn mann_whitney(a: &[f64], b: &[f64]) -> f64 {
+    let (n1, n2) = (a.len() as f64, b.len() as f64);
+    let n = n1 + n2;
+
+    let mut all = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect::<Vec<_>>();
+    all.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Tied samples share the average of their ranks.
+    let (mut rank_sum, mut ties) = (0.0, 0.0);
+    let mut start = 0;
+    while start < all.len() {
+        let end = start
+            + all[start..]
+                .iter()
+                .take_while(|x| x.0 == all[start].0)
+                .count();
+        let rank = (start + end + 1) as f64 / 2.0;
+        rank_sum += rank * all[start..end].iter().filter(|x| x.1).count() as f64;
+        let tied = (end - start) as f64;
+        ties += tied.powi(3) - tied;
+        start = end;
+    }
+
+    let u = rank_sum - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - ties / (n * (n - 1.0)));
+    if variance <= 0.0 || !variance.is_finite() {
+        return 1.0;
+    }
+    // With a continuity correction.
+    let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+    erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// The complementary error function, to within 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t
+        * (-z * z - 1.265_512_23
+            + t * (1.000_023_68
+                + t * (0.374_091_96
+                    + t * (0.096_784_18
+                        + t * (-0.186_288_06
+                            + t * (0.278_868_07
+                                + t * (-1.135_203_98
+                                    + t * (1.488_515_87
+                                        + t * (-0.822_152_23 + t * 0.170_872_77)))))))))
+            .exp();
+    match x >= 0.0 {
+        true => r,
+        false => 2.0 - r,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod percentile {
+        use super::*;
+
+        struct Case<'c> {
+            sorted: &'c [f64],
+            p: f64,
+            expected: f64,
+        }
+
+        #[test]
+        fn single() {
+            run(&Case {
+                sorted: &[7.0],
+                p: 95.0,
+                expected: 7.0,
+            })
+        }
+
+        #[test]
+        fn exact() {
+            run(&Case {
+                sorted: &[1.0, 2.0, 3.0, 4.0, 5.0],
+                p: 25.0,
+                expected: 2.0,
+            })
+        }
+
+        #[test]
+        fn interpolated() {
+            run(&Case {
+                sorted: &[1.0, 2.0, 3.0, 4.0],
+                p: 50.0,
+                expected: 2.5,
+            })
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, percentile(test.sorted, test.p))
+        }
+    }
+
+    #[test]
+    fn stats() {
+        // Samples spread evenly from 11 to 29, and one slow one, e.g. from a
+        // context switch.
+        let mut samples = (11..=29).map(f64::from).collect::<Vec<_>>();
+        samples.insert(5, 100.0);
+
+        let stats = Stats::new(&samples).unwrap();
+
+        assert_eq!(20.5, stats.median);
+        assert_eq!((11.0, 100.0), (stats.min, stats.max));
+        assert_eq!(5.0, stats.mad);
+        assert!(stats.std_dev > 15.0);
+        assert_eq!(
+            Outliers {
+                high_severe: 1,
+                ..Outliers::default()
+            },
+            stats.outliers
+        );
+        assert_eq!(None, Stats::new(&[]));
+    }
+
+    #[test]
+    fn mann_whitney() {
+        let steady = (0..50)
+            .map(|i| 100.0 + f64::from(i % 7))
+            .collect::<Vec<_>>();
+        let faster = steady.iter().map(|x| x * 0.9).collect::<Vec<_>>();
+        let shuffled = steady.iter().rev().copied().collect::<Vec<_>>();
+
+        assert!(super::mann_whitney(&steady, &faster) < 0.001);
+        assert!(super::mann_whitney(&steady, &shuffled) > 0.9);
+        assert_eq!(1.0, super::mann_whitney(&[5.0; 10], &[5.0; 10]));
+    }
+
+    #[test]
+    fn erfc() {
+        assert!((super::erfc(0.0) - 1.0).abs() < 1e-7);
+        assert!((super::erfc(1.0) - 0.157_299_2).abs() < 1e-6);
+        assert!((super::erfc(-1.0) - 1.842_700_8).abs() < 1e-6);
+    }
+}
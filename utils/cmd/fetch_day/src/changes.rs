@@ -0,0 +1,134 @@
+//! Every file `fetch_day` writes goes through [`Changes`], which reports what
+//! it does and, for `--dry-run`, only reports what it would do.
+
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    io,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    Create(PathBuf),
+    Modify(PathBuf),
+}
+
+pub struct Changes {
+    dry_run: bool,
+    made: Vec<Change>,
+}
+
+impl Changes {
+    pub fn new(dry_run: bool) -> Self {
+        Changes {
+            dry_run,
+            made: Vec::new(),
+        }
+    }
+
+    /// Writes `contents` to `file`, unless it already has them.
+    pub fn write(&mut self, file: &Path, contents: &str) -> io::Result<()> {
+        let change = match read_to_string(file) {
+            Ok(existing) if existing == contents => return Ok(()),
+            Ok(_) => Change::Modify(file.to_owned()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Change::Create(file.to_owned()),
+            Err(e) => return Err(e),
+        };
+        self.make(change, contents)
+    }
+
+    /// Writes `contents` to `file` if it doesn't exist. Existing files are
+    /// never replaced.
+    pub fn create(&mut self, file: &Path, contents: &str) -> io::Result<()> {
+        self.create_with(file, || Ok::<_, io::Error>(contents.to_owned()))
+    }
+
+    /// Writes the result of `contents` to `file` if it doesn't exist.
+    /// `contents` is only called if the file is to be written, so a dry run
+    /// doesn't, for example, download it.
+    pub fn create_with<E: From<io::Error>>(
+        &mut self,
+        file: &Path,
+        contents: impl FnOnce() -> Result<String, E>,
+    ) -> Result<(), E> {
+        if file.exists() {
+            return Ok(());
+        }
+        let contents = match self.dry_run {
+            true => String::new(),
+            false => contents()?,
+        };
+        Ok(self.make(Change::Create(file.to_owned()), &contents)?)
+    }
+
+    fn make(&mut self, change: Change, contents: &str) -> io::Result<()> {
+        let (verb, file) = match (&change, self.dry_run) {
+            (Change::Create(file), true) => ("Would create", file),
+            (Change::Modify(file), true) => ("Would modify", file),
+            (Change::Create(file), false) => ("Created", file),
+            (Change::Modify(file), false) => ("Modified", file),
+        };
+        if !self.dry_run {
+            if let Some(dir) = file.parent() {
+                create_dir_all(dir)?;
+            }
+            write(file, contents)?;
+        }
+        println!(
+            "{} {}",
+            verb,
+            file.strip_prefix(".").unwrap_or(file).display()
+        );
+        self.made.push(change);
+        Ok(())
+    }
+
+    pub fn made(&self) -> &[Change] {
+        &self.made
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs::remove_dir_all};
+
+    #[test]
+    fn changes() {
+        let dir = env::temp_dir().join(format!("fetch_day-changes-{}", std::process::id()));
+        let (solved, readme) = (dir.join("src").join("lib.rs"), dir.join("README.md"));
+        create_dir_all(solved.parent().unwrap()).unwrap();
+        write(&solved, "solved").unwrap();
+        write(&readme, "part 1").unwrap();
+
+        let run = |dry_run| {
+            let mut changes = Changes::new(dry_run);
+            changes.create(&solved, "template").unwrap();
+            changes.create(&dir.join("Cargo.toml"), "template").unwrap();
+            changes.write(&readme, "part 2").unwrap();
+            changes
+                .create_with(&dir.join("input.txt"), || {
+                    Ok::<_, io::Error>("1".to_owned())
+                })
+                .unwrap();
+            changes.made
+        };
+
+        let expected = vec![
+            Change::Create(dir.join("Cargo.toml")),
+            Change::Modify(readme.clone()),
+            Change::Create(dir.join("input.txt")),
+        ];
+        assert_eq!(expected, run(true));
+        assert_eq!("part 1", read_to_string(&readme).unwrap());
+        assert!(!dir.join("Cargo.toml").exists());
+
+        assert_eq!(expected, run(false));
+        assert_eq!(Vec::<Change>::new(), run(false));
+        let contents =
+            [&solved, &readme, &dir.join("input.txt")].map(|file| read_to_string(file).unwrap());
+        remove_dir_all(&dir).unwrap();
+
+        assert_eq!(["solved", "part 2", "1"], contents);
+    }
+}
@@ -0,0 +1,433 @@
+//! The one client all requests to the site go through, which tries to be a
+//! good citizen: it says who it is, waits between requests, retries with
+//! backoff, and caches what it has already downloaded.
+
+use crate::{
+    puzzle::Puzzle,
+    session::{self, TokenError},
+    wait,
+};
+use reqwest::{
+    blocking::{RequestBuilder, Response},
+    StatusCode,
+};
+use solution::input_hash;
+use std::{
+    cell::OnceCell,
+    fmt,
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Where responses are cached, relative to the repository root.
+pub const CACHE_DIR: &str = ".cache/aoc";
+
+const USER_AGENT: &str = concat!(
+    "fetch_day/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/maneac/aoc2021)"
+);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The shortest time between two requests, even from separate runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How many times to try a request that failed in a way that might pass.
+const ATTEMPTS: u32 = 3;
+
+/// Records when the last request was sent, in the cache directory.
+const LAST_REQUEST_FILE: &str = "last-request";
+
+pub struct Client {
+    http: reqwest::blocking::Client,
+    base_url: String,
+    /// The `--token` flag, if given.
+    token_flag: Option<String>,
+    /// The session token, found the first time a request needs it, so that
+    /// runs served from the cache work without one.
+    token: OnceCell<String>,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Client {
+    pub fn new(base_url: &str, token_flag: Option<String>, cache_dir: &Path) -> Self {
+        let http = reqwest::blocking::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(TIMEOUT)
+            .build()
+            .unwrap();
+
+        Client {
+            http,
+            base_url: base_url.to_owned(),
+            token_flag,
+            token: OnceCell::new(),
+            cache_dir: cache_dir.to_owned(),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// The puzzle's page. A cached copy is used unless `fresh` rejects it.
+    pub fn instructions(
+        &self,
+        puzzle: &Puzzle,
+        fresh: impl Fn(&str) -> bool,
+    ) -> Result<String, FetchError> {
+        self.get(&puzzle.url_at(&self.base_url), fresh)
+    }
+
+    /// The puzzle input, which never changes once downloaded.
+    pub fn input(&self, puzzle: &Puzzle) -> Result<String, FetchError> {
+        self.get(&format!("{}/input", puzzle.url_at(&self.base_url)), |_| {
+            true
+        })
+    }
+
+    /// Posts an answer, returning the page the site replies with. This is
+    /// neither cached nor retried, as the site counts every answer given.
+    pub fn submit(&self, puzzle: &Puzzle, part: u8, answer: &str) -> Result<String, FetchError> {
+        let url = format!("{}/answer", puzzle.url_at(&self.base_url));
+        let form = [("level", part.to_string()), ("answer", answer.to_owned())];
+        let res = self.send(|| self.http.post(&url).form(&form), 1)?;
+        read_page(res)
+    }
+
+    fn get(&self, url: &str, fresh: impl Fn(&str) -> bool) -> Result<String, FetchError> {
+        // Other sessions may get other inputs, so each page is stored after
+        // the hash of the session it was fetched with, and is only used for
+        // that session. Without a token it is used as it is.
+        let cached = self.cache_dir.join(input_hash(url));
+        let session = self.token().ok().map(input_hash);
+        let contents = read_to_string(&cached).unwrap_or_default();
+        if let Some((fetched_by, page)) = contents.split_once('\n') {
+            let ours = session
+                .as_deref()
+                .is_none_or(|session| session == fetched_by);
+            if ours && fresh(page) {
+                return Ok(page.to_owned());
+            }
+        }
+
+        let page = read_page(self.send(|| self.http.get(url), ATTEMPTS)?)?;
+        // The cache is only an optimisation.
+        let session = input_hash(self.token()?);
+        let _ = create_dir_all(&self.cache_dir)
+            .and_then(|_| write(&cached, format!("{}\n{}", session, page)));
+        Ok(page)
+    }
+
+    /// The session token, resolved the first time it's needed.
+    fn token(&self) -> Result<&str, TokenError> {
+        if let Some(token) = self.token.get() {
+            return Ok(token);
+        }
+        let token = session::token(self.token_flag.as_deref())?;
+        Ok(self.token.get_or_init(|| token))
+    }
+
+    /// Sends a request, trying up to `attempts` times while it times out,
+    /// fails to connect, or the site is overloaded.
+    fn send(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        attempts: u32,
+    ) -> Result<Response, FetchError> {
+        let token = self.token()?;
+        let mut attempt = 1;
+        loop {
+            self.throttle();
+            let result = request()
+                .header("Cookie", format!("session={}", token))
+                .send();
+
+            let transient = match &result {
+                Ok(res) => {
+                    res.status().is_server_error() || res.status() == StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(e) => e.is_timeout() || e.is_connect(),
+            };
+            if !transient || attempt >= attempts {
+                return Ok(result?);
+            }
+            sleep(wait::backoff(attempt));
+            attempt += 1;
+        }
+    }
+
+    /// Sleeps until [`MIN_INTERVAL`] has passed since the last request, then
+    /// records this one.
+    fn throttle(&self) {
+        let file = self.cache_dir.join(LAST_REQUEST_FILE);
+        let last = read_to_string(&file)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(since) = last.and_then(|last| SystemTime::now().duration_since(last).ok()) {
+            if since < self.min_interval {
+                sleep(self.min_interval - since);
+            }
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let _ =
+            create_dir_all(&self.cache_dir).and_then(|_| write(&file, now.as_millis().to_string()));
+    }
+}
+
+/// Reads a page, checking it is one worth keeping.
+fn read_page(res: Response) -> Result<String, FetchError> {
+    match res.status() {
+        // Inputs and answers sent without a valid session are refused with a
+        // 400 and a "please log in" message.
+        StatusCode::BAD_REQUEST => return Err(FetchError::LoggedOut),
+        // Puzzles are not found until they unlock.
+        StatusCode::NOT_FOUND => return Err(FetchError::Locked),
+        _ => {}
+    }
+    let page = res.error_for_status()?.text()?;
+    if session::is_logged_out(&page) {
+        return Err(FetchError::LoggedOut);
+    }
+    Ok(page)
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// No session token could be found for a request.
+    Token(TokenError),
+    Http(reqwest::Error),
+    /// The site treated the request as coming from someone not logged in.
+    LoggedOut,
+    /// The puzzle has not unlocked yet.
+    Locked,
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Token(e) => write!(f, "{}", e),
+            FetchError::Http(e) => write!(f, "{}", e),
+            FetchError::LoggedOut => write!(
+                f,
+                "Advent of Code did not accept the session token, which has probably expired. \
+                 Log in again and copy the new 'session' cookie"
+            ),
+            FetchError::Locked => write!(
+                f,
+                "the puzzle has not unlocked yet: pass --wait to wait for it"
+            ),
+        }
+    }
+}
+
+impl From<TokenError> for FetchError {
+    fn from(e: TokenError) -> Self {
+        FetchError::Token(e)
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(e: reqwest::Error) -> Self {
+        FetchError::Http(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::submit::{classify, Outcome};
+    use std::{
+        env,
+        fs::remove_dir_all,
+        io::{Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves one request per response on a local port, replying with each
+    /// status and page in turn. Returns the server's URL, and a handle giving
+    /// the requests it received.
+    fn stand_in(responses: &[(&'static str, &'static str)]) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = responses.to_vec();
+
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, page) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                let complete = |request: &[u8]| {
+                    let request = String::from_utf8_lossy(request);
+                    request.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                let (name, value) = line.split_once(':')?;
+                                name.eq_ignore_ascii_case("content-length")
+                                    .then(|| value.trim().parse::<usize>().ok())?
+                            })
+                            .unwrap_or(0);
+                        body.len() >= length
+                    })
+                };
+                while !complete(&request) {
+                    let read = stream.read(&mut buf).unwrap();
+                    request.extend_from_slice(&buf[..read]);
+                }
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    page.len(),
+                    page
+                )
+                .unwrap();
+                requests.push(String::from_utf8(request).unwrap());
+            }
+            requests
+        });
+
+        (base_url, server)
+    }
+
+    /// A client for `base_url` with its own cache, and no wait between
+    /// requests.
+    fn client(base_url: &str, name: &str) -> (Client, PathBuf) {
+        let cache_dir = env::temp_dir().join(format!("fetch_day-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&cache_dir);
+        let mut client = Client::new(base_url, Some("token".to_owned()), &cache_dir);
+        client.min_interval = Duration::ZERO;
+        (client, cache_dir)
+    }
+
+    const PUZZLE: Puzzle = Puzzle { year: 2021, day: 7 };
+
+    mod submit {
+        use super::*;
+
+        #[test]
+        fn correct() {
+            let (base_url, server) = stand_in(&[(
+                "200 OK",
+                "<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>",
+            )]);
+            let (client, cache_dir) = client(&base_url, "submit");
+
+            let page = client.submit(&PUZZLE, 1, "37").unwrap();
+            let requests = server.join().unwrap();
+            remove_dir_all(cache_dir).unwrap();
+
+            let request = &requests[0];
+            assert!(request.starts_with("POST /2021/day/7/answer HTTP/1.1\r\n"));
+            let headers = request.to_lowercase();
+            assert!(headers.contains("\r\ncookie: session=token\r\n"));
+            assert!(headers.contains(&format!(
+                "\r\nuser-agent: {}\r\n",
+                USER_AGENT.to_lowercase()
+            )));
+            assert!(request.ends_with("\r\n\r\nlevel=1&answer=37"));
+            assert_eq!(Some(Outcome::Correct), classify(&page));
+        }
+
+        #[test]
+        fn logged_out() {
+            let (base_url, server) = stand_in(&[("400 Bad Request", "Please log in.")]);
+            let (client, cache_dir) = client(&base_url, "logged-out");
+
+            let result = client.submit(&PUZZLE, 2, "168");
+            server.join().unwrap();
+            let _ = remove_dir_all(cache_dir);
+
+            assert!(matches!(result, Err(FetchError::LoggedOut)));
+        }
+    }
+
+    #[test]
+    fn cached() {
+        let (base_url, server) = stand_in(&[
+            ("200 OK", "<article>Part one</article>"),
+            (
+                "200 OK",
+                "<article>Part one</article><article>Part two</article>",
+            ),
+        ]);
+        let (client, cache_dir) = client(&base_url, "cached");
+        let has_part_2 = |page: &str| page.contains("Part two");
+
+        let first = client.instructions(&PUZZLE, |_| true).unwrap();
+        let cached = client.instructions(&PUZZLE, |_| true).unwrap();
+        let refreshed = client.instructions(&PUZZLE, has_part_2).unwrap();
+        // Only two requests are served, so this must come from the cache.
+        let cached_refresh = client.instructions(&PUZZLE, has_part_2).unwrap();
+        server.join().unwrap();
+        remove_dir_all(cache_dir).unwrap();
+
+        assert_eq!(first, cached);
+        assert!(!has_part_2(&first));
+        assert!(has_part_2(&refreshed));
+        assert_eq!(refreshed, cached_refresh);
+    }
+
+    #[test]
+    fn cached_by_session() {
+        let (base_url, server) = stand_in(&[("200 OK", "1\n2\n"), ("200 OK", "3\n4\n")]);
+        let (client, cache_dir) = client(&base_url, "cached-by-session");
+        let mut other = Client::new(&base_url, Some("other".to_owned()), &cache_dir);
+        other.min_interval = Duration::ZERO;
+
+        let input = client.input(&PUZZLE).unwrap();
+        let other_input = other.input(&PUZZLE).unwrap();
+        server.join().unwrap();
+        remove_dir_all(cache_dir).unwrap();
+
+        assert_eq!("1\n2\n", input);
+        assert_eq!("3\n4\n", other_input);
+    }
+
+    #[test]
+    fn retried() {
+        let (base_url, server) = stand_in(&[
+            ("503 Service Unavailable", "Try again"),
+            ("200 OK", "1\n2\n"),
+        ]);
+        let (client, cache_dir) = client(&base_url, "retried");
+
+        let input = client.input(&PUZZLE).unwrap();
+        let requests = server.join().unwrap();
+        remove_dir_all(cache_dir).unwrap();
+
+        assert_eq!("1\n2\n", input);
+        assert!(requests
+            .iter()
+            .all(|request| request.starts_with("GET /2021/day/7/input HTTP/1.1\r\n")));
+    }
+
+    #[test]
+    fn locked_not_cached() {
+        let (base_url, server) = stand_in(&[
+            (
+                "404 Not Found",
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            ("200 OK", "<article>Part one</article>"),
+        ]);
+        let (client, cache_dir) = client(&base_url, "locked");
+
+        let locked = client.instructions(&PUZZLE, |_| true);
+        let unlocked = client.instructions(&PUZZLE, |_| true).unwrap();
+        server.join().unwrap();
+        remove_dir_all(cache_dir).unwrap();
+
+        assert!(matches!(locked, Err(FetchError::Locked)));
+        assert_eq!("<article>Part one</article>", unlocked);
+    }
+}
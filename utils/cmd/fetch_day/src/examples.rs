@@ -0,0 +1,131 @@
+//! Saves the examples found in a puzzle's description as test fixtures, in
+//! the layout [`Answers::example`] reads.
+
+use crate::{changes::Changes, markdown::Examples};
+use solution::{Answer, Answers, AnswersError, ANSWERS_FILE, EXAMPLES_DIR};
+use std::{convert::Infallible, fs::read_to_string, io, path::Path};
+
+/// Saves each example input as `day_N/<n>.txt` in the examples directory of
+/// `data_dir`, and records the candidate answers for the first in its
+/// registry.
+///
+/// Saved inputs and recorded answers are never replaced, so fixtures corrected
+/// by hand survive fetching the puzzle again for part 2.
+pub fn save(
+    data_dir: &Path,
+    day: u8,
+    examples: &Examples,
+    changes: &mut Changes,
+) -> Result<(), AnswersError> {
+    let io_error = |file: &Path| {
+        let file = file.to_owned();
+        move |source| AnswersError::Io { file, source }
+    };
+
+    let examples_dir = data_dir.join(EXAMPLES_DIR);
+    let day_dir = examples_dir.join(format!("day_{}", day));
+    for (n, input) in examples.inputs.iter().enumerate() {
+        let file = day_dir.join(format!("{}.txt", n + 1));
+        changes.create(&file, input).map_err(io_error(&file))?;
+    }
+
+    // On a dry run, the first example may only have been found.
+    let first = day_dir.join("1.txt");
+    let input = match read_to_string(&first) {
+        Ok(input) => input,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match examples.inputs.first() {
+            Some(input) => input.to_owned(),
+            None => return Ok(()),
+        },
+        Err(e) => return Err(io_error(&first)(e)),
+    };
+
+    let answers_file = examples_dir.join(ANSWERS_FILE);
+    let mut answers = match answers_file.exists() {
+        true => Answers::load(&answers_file)?,
+        false => Answers::default(),
+    };
+    let recorded = answers.get(day, &input).cloned();
+
+    let mut expected = recorded.clone().unwrap_or_default();
+    let candidates = examples.answers.iter().map(|answer| {
+        answer.as_deref().map_or(Answer::Empty, |answer| {
+            answer.parse().unwrap_or_else(|e: Infallible| match e {})
+        })
+    });
+    for (answer, candidate) in [&mut expected.part_1, &mut expected.part_2]
+        .into_iter()
+        .zip(candidates)
+    {
+        if *answer == Answer::Empty {
+            *answer = candidate;
+        }
+    }
+
+    if recorded.as_ref() != Some(&expected) {
+        answers.insert(day, &input, expected);
+        let contents = answers
+            .to_toml()
+            .map_err(|source| AnswersError::Serialize {
+                file: answers_file.clone(),
+                source,
+            })?;
+        changes
+            .write(&answers_file, &contents)
+            .map_err(io_error(&answers_file))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changes::Change;
+    use solution::Expected;
+    use std::{env, fs::remove_dir_all};
+
+    #[test]
+    fn part_2() {
+        let data_dir = env::temp_dir().join(format!("fetch_day-examples-{}", std::process::id()));
+        let day_dir = data_dir.join(EXAMPLES_DIR).join("day_3");
+
+        let part_1 = Examples {
+            inputs: vec!["1\n2\n".to_owned()],
+            answers: vec![Some("3".to_owned())],
+        };
+        let mut changes = Changes::new(false);
+        save(&data_dir, 3, &part_1, &mut changes).unwrap();
+        assert_eq!(
+            &[
+                Change::Create(day_dir.join("1.txt")),
+                Change::Create(data_dir.join(EXAMPLES_DIR).join(ANSWERS_FILE)),
+            ],
+            changes.made()
+        );
+
+        // Part 2 reveals another example and answer, while the candidates for
+        // part 1 differ, as if the description had changed.
+        let part_2 = Examples {
+            inputs: vec!["9\n".to_owned(), "4\n5\n".to_owned()],
+            answers: vec![Some("30".to_owned()), Some("12".to_owned())],
+        };
+        save(&data_dir, 3, &part_2, &mut Changes::new(false)).unwrap();
+        let mut changes = Changes::new(false);
+        save(&data_dir, 3, &part_2, &mut changes).unwrap();
+        assert_eq!(&[] as &[Change], changes.made());
+
+        let example = Answers::example(&data_dir, 3, 1).unwrap();
+        let second = read_to_string(day_dir.join("2.txt")).unwrap();
+        remove_dir_all(&data_dir).unwrap();
+
+        assert_eq!("1\n2\n", example.input);
+        assert_eq!(
+            Expected {
+                part_1: Answer::Integer(3),
+                part_2: Answer::Integer(12),
+            },
+            example.expected
+        );
+        assert_eq!("4\n5\n", second);
+    }
+}
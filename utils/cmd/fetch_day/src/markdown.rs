@@ -0,0 +1,768 @@
+//! Converts Advent of Code puzzle pages into the Markdown kept in each day's
+//! README.
+//!
+//! The HTML is parsed forgivingly into a tree: unclosed tags are closed at the
+//! end of their parent, stray closing tags are ignored, and tags without a
+//! Markdown equivalent are replaced by their contents.
+
+use html_escape::{decode_html_entities, encode_double_quoted_attribute, encode_text};
+
+/// Converts every `<article>` in `page`, the puzzle's description, resolving
+/// relative links against `page_url`.
+pub fn instructions(page: &str, page_url: &str) -> String {
+    let tree = parse(page);
+    let mut articles = Vec::new();
+    find_articles(&tree, &mut articles);
+
+    let renderer = Renderer { page_url };
+    let mut blocks = Vec::new();
+    for article in articles {
+        renderer.blocks(article, &mut blocks);
+    }
+
+    let mut output = blocks.join("\n\n");
+    output.push('\n');
+    output
+}
+
+/// The examples in a puzzle's description.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Code blocks that look like puzzle input: more than one line, and no
+    /// emphasis, which marks a worked example rather than an input.
+    pub inputs: Vec<String>,
+    /// For each part, its last emphasised code, which is usually the answer
+    /// to the first example.
+    pub answers: Vec<Option<String>>,
+}
+
+/// Finds the candidate example inputs and answers in `page`.
+pub fn examples(page: &str) -> Examples {
+    fn find(nodes: &[Node], inputs: &mut Vec<String>, answer: &mut Option<String>) {
+        for node in nodes {
+            if let Node::Element { name, children, .. } = node {
+                match name.as_str() {
+                    "pre" => {
+                        let text = node.text();
+                        if !node.contains("em") && text.trim_end().contains('\n') {
+                            inputs.push(text);
+                        }
+                    }
+                    "code" if node.contains("em") => *answer = Some(node.text()),
+                    "em" if node.contains("code") => *answer = Some(node.text()),
+                    _ => find(children, inputs, answer),
+                }
+            }
+        }
+    }
+
+    let tree = parse(page);
+    let mut articles = Vec::new();
+    find_articles(&tree, &mut articles);
+
+    let mut examples = Examples::default();
+    for article in articles {
+        let mut answer = None;
+        find(article, &mut examples.inputs, &mut answer);
+        // Sums are often spelled out, e.g. "6+57+1197+25137 = 26397".
+        examples.answers.push(answer.map(|answer| {
+            let (_, result) = answer.rsplit_once('=').unwrap_or(("", &answer));
+            result.trim().to_owned()
+        }));
+    }
+    examples
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    fn element(name: String, attrs: Vec<(String, String)>) -> Self {
+        Node::Element {
+            name,
+            attrs,
+            children: Vec::new(),
+        }
+    }
+
+    fn attr(&self, attr: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(name, _)| name == attr)
+                .map(|(_, value)| value.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    /// The decoded text of this node and all its descendants.
+    fn text(&self) -> String {
+        match self {
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+            Node::Text(text) => text.to_owned(),
+        }
+    }
+
+    fn contains(&self, element: &str) -> bool {
+        match self {
+            Node::Element { name, children, .. } => {
+                name == element || children.iter().any(|child| child.contains(element))
+            }
+            Node::Text(_) => false,
+        }
+    }
+}
+
+/// Elements that never have contents or a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose contents are not HTML, and never part of the description.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+fn parse(html: &str) -> Vec<Node> {
+    // The open elements, with the children parsed so far. The root has no
+    // name.
+    let mut stack = vec![Node::element(String::new(), Vec::new())];
+
+    fn push(stack: &mut [Node], node: Node) {
+        if let Some(Node::Element { children, .. }) = stack.last_mut() {
+            children.push(node);
+        }
+    }
+    fn close(stack: &mut Vec<Node>) {
+        let element = stack.pop().unwrap();
+        push(stack, element);
+    }
+
+    let mut rest = html;
+    while !rest.is_empty() {
+        let text_len = rest.find('<').unwrap_or(rest.len());
+        if text_len > 0 {
+            let text = decode_html_entities(&rest[..text_len]).into_owned();
+            push(&mut stack, Node::Text(text));
+            rest = &rest[text_len..];
+            continue;
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if let Some(end) = rest.strip_prefix("</").and(rest.find('>')) {
+            let name = rest[2..end].trim().to_ascii_lowercase();
+            rest = &rest[end + 1..];
+            let open = stack.iter().rposition(
+                |open| matches!(open, Node::Element { name: open, .. } if *open == name),
+            );
+            if let Some(open) = open.filter(|_| !name.is_empty()) {
+                // Anything left open inside the closed element ends with it.
+                while stack.len() > open {
+                    close(&mut stack);
+                }
+            }
+            continue;
+        }
+
+        let (name, attrs, self_closing, tag_len) = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                // A lone '<' is just text.
+                push(&mut stack, Node::Text("<".to_owned()));
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag_len..];
+
+        if name.starts_with('!') || name.starts_with('?') {
+            continue;
+        }
+        if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+            let closing = format!("</{}", name);
+            rest = find_ignore_case(rest, &closing)
+                .and_then(|start| rest[start..].find('>').map(|end| &rest[start + end + 1..]))
+                .unwrap_or("");
+            continue;
+        }
+        if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+            push(&mut stack, Node::element(name, attrs));
+            continue;
+        }
+        stack.push(Node::element(name, attrs));
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+    match stack.pop() {
+        Some(Node::Element { children, .. }) => children,
+        _ => Vec::new(),
+    }
+}
+
+type Tag = (String, Vec<(String, String)>, bool, usize);
+
+/// Parses an opening tag at the start of `input`, returning its name,
+/// attributes, whether it closes itself, and its length.
+fn parse_tag(input: &str) -> Option<Tag> {
+    let bytes = input.as_bytes();
+    let mut idx = 1;
+    let name_end = |idx: usize| {
+        bytes[idx..]
+            .iter()
+            .position(|b| b.is_ascii_whitespace() || *b == b'>' || *b == b'/' || *b == b'=')
+            .map_or(bytes.len(), |len| idx + len)
+    };
+
+    let end = name_end(idx);
+    if end == idx || !(bytes[idx].is_ascii_alphabetic() || matches!(bytes[idx], b'!' | b'?')) {
+        return None;
+    }
+    let name = input[idx..end].to_ascii_lowercase();
+    idx = end;
+
+    let mut attrs = Vec::new();
+    let mut self_closing = false;
+    loop {
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        match bytes.get(idx)? {
+            b'>' => return Some((name, attrs, self_closing, idx + 1)),
+            b'/' => {
+                self_closing = true;
+                idx += 1;
+                continue;
+            }
+            _ => self_closing = false,
+        }
+
+        let end = name_end(idx).max(idx + 1);
+        let attr = input[idx..end].to_ascii_lowercase();
+        idx = end;
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        if bytes.get(idx) != Some(&b'=') {
+            attrs.push((attr, String::new()));
+            continue;
+        }
+
+        idx += 1;
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        let value = match bytes.get(idx)? {
+            quote @ (b'"' | b'\'') => {
+                let len = input[idx + 1..].find(*quote as char)?;
+                let value = &input[idx + 1..idx + 1 + len];
+                idx += len + 2;
+                value
+            }
+            _ => {
+                let len = bytes[idx..]
+                    .iter()
+                    .position(|b| b.is_ascii_whitespace() || *b == b'>')
+                    .unwrap_or(bytes.len() - idx);
+                let value = &input[idx..idx + len];
+                idx += len;
+                value
+            }
+        };
+        attrs.push((attr, decode_html_entities(value).into_owned()));
+    }
+}
+
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+fn find_articles<'n>(nodes: &'n [Node], articles: &mut Vec<&'n [Node]>) {
+    for node in nodes {
+        if let Node::Element { name, children, .. } = node {
+            match name.as_str() {
+                "article" => articles.push(children),
+                _ => find_articles(children, articles),
+            }
+        }
+    }
+}
+
+struct Renderer<'u> {
+    page_url: &'u str,
+}
+
+impl Renderer<'_> {
+    /// Renders block elements, each as one entry of `blocks`. Runs of inline
+    /// content between them become paragraphs.
+    fn blocks(&self, nodes: &[Node], blocks: &mut Vec<String>) {
+        fn flush(paragraph: &mut String, blocks: &mut Vec<String>) {
+            let text = paragraph.trim();
+            if !text.is_empty() {
+                blocks.push(text.to_owned());
+            }
+            paragraph.clear();
+        }
+
+        let mut paragraph = String::new();
+
+        for node in nodes {
+            let (name, children) = match node {
+                Node::Element { name, children, .. } if is_block(name) => (name.as_str(), children),
+                _ => {
+                    paragraph.push_str(&self.inline(node));
+                    continue;
+                }
+            };
+            flush(&mut paragraph, blocks);
+
+            match name {
+                "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                    let title = self.inlines(children).trim().to_owned();
+                    // The first heading names the puzzle, and links to it.
+                    blocks.push(if blocks.is_empty() && title.starts_with("--- Day") {
+                        format!("# [{}]({})", title, self.page_url)
+                    } else {
+                        format!("{} {}", "#".repeat(name[1..].parse().unwrap()), title)
+                    });
+                }
+                "p" => flush(&mut self.inlines(children), blocks),
+                "pre" => blocks.push(format!("<pre>{}</pre>", self.preformatted(children))),
+                "ul" | "ol" => blocks.push(self.list(node, 1)),
+                "hr" => blocks.push("---".to_owned()),
+                _ => self.blocks(children, blocks),
+            }
+        }
+        flush(&mut paragraph, blocks);
+    }
+
+    fn inlines(&self, nodes: &[Node]) -> String {
+        nodes.iter().map(|node| self.inline(node)).collect()
+    }
+
+    fn inline(&self, node: &Node) -> String {
+        let (name, children) = match node {
+            Node::Element { name, children, .. } => (name.as_str(), children),
+            Node::Text(text) => return escape(text),
+        };
+
+        match name {
+            "em" | "strong" | "b" => emphasise(&self.inlines(children), "**"),
+            "i" => emphasise(&self.inlines(children), "*"),
+            // Markdown can't format inside code, so emphasis inside it is
+            // moved outside.
+            "code" if node.contains("em") => emphasise(&code_span(&node.text()), "**"),
+            "code" => code_span(&node.text()),
+            "a" => match node.attr("href") {
+                Some(href) => format!("[{}]({})", self.inlines(children), self.url(href)),
+                None => self.inlines(children),
+            },
+            // The titles hold Easter eggs, which show when hovered over.
+            "span" => match node.attr("title") {
+                Some(title) => format!(
+                    r#"<span title="{}">{}</span>"#,
+                    encode_double_quoted_attribute(title),
+                    self.inlines(children)
+                ),
+                None => self.inlines(children),
+            },
+            "br" => "  \n".to_owned(),
+            "img" => format!(
+                "![{}]({})",
+                escape(node.attr("alt").unwrap_or_default()),
+                self.url(node.attr("src").unwrap_or_default())
+            ),
+            "ul" | "ol" => format!("\n{}\n", self.list(node, 1)),
+            _ => self.inlines(children),
+        }
+    }
+
+    /// Renders the contents of a `<pre>`, which stays HTML so that emphasis
+    /// and alignment survive.
+    fn preformatted(&self, nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Element { name, children, .. } => {
+                    let contents = self.preformatted(children);
+                    match name.as_str() {
+                        "em" | "strong" | "b" => format!("<b>{}</b>", contents),
+                        "code" => format!("<code>{}</code>", contents),
+                        "span" => match node.attr("title") {
+                            Some(title) => format!(
+                                r#"<span title="{}">{}</span>"#,
+                                encode_double_quoted_attribute(title),
+                                contents
+                            ),
+                            None => contents,
+                        },
+                        "br" => "\n".to_owned(),
+                        _ => contents,
+                    }
+                }
+                Node::Text(text) => encode_text(text).into_owned(),
+            })
+            .collect()
+    }
+
+    /// Renders a list and any lists nested in it, indented by `depth`.
+    fn list(&self, list: &Node, depth: usize) -> String {
+        let (name, items) = match list {
+            Node::Element { name, children, .. } => (name, children),
+            Node::Text(_) => return String::new(),
+        };
+
+        let mut lines = Vec::new();
+        let mut number = 0;
+        for item in items {
+            let children = match item {
+                Node::Element { name, children, .. } if name == "li" => children,
+                _ => continue,
+            };
+
+            number += 1;
+            let marker = match name.as_str() {
+                "ol" => format!("{}.", number),
+                _ => "-".to_owned(),
+            };
+            let (nested, contents): (Vec<&Node>, Vec<&Node>) = children.iter().partition(
+                |child| matches!(child, Node::Element { name, .. } if name == "ul" || name == "ol"),
+            );
+
+            let text = contents
+                .into_iter()
+                .map(|node| self.inline(node))
+                .collect::<String>();
+            lines.push(format!("{}{} {}", "  ".repeat(depth), marker, text.trim()));
+            for list in nested {
+                lines.push(self.list(list, depth + 1));
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Makes `href` absolute, as the README is not served from the puzzle's
+    /// page.
+    fn url(&self, href: &str) -> String {
+        let page = self.page_url.split('#').next().unwrap();
+        let origin = page
+            .find("://")
+            .and_then(|scheme| {
+                page[scheme + 3..]
+                    .find('/')
+                    .map(|path| &page[..scheme + 3 + path])
+            })
+            .unwrap_or(page);
+
+        let url = if href.contains("://") || href.starts_with("mailto:") {
+            href.to_owned()
+        } else if let Some(path) = href.strip_prefix("//") {
+            format!(
+                "{}//{}",
+                &page[..page.find("//").map_or(0, |idx| idx)],
+                path
+            )
+        } else if href.starts_with('/') {
+            format!("{}{}", origin, href)
+        } else if href.starts_with('#') || href.is_empty() {
+            format!("{}{}", page, href)
+        } else {
+            match page[origin.len()..].rfind('/') {
+                Some(idx) => format!("{}{}", &page[..=origin.len() + idx], href),
+                None => format!("{}/{}", origin, href),
+            }
+        };
+
+        // Parentheses and spaces would end the link early.
+        url.replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29")
+    }
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "h1" | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "p"
+            | "pre"
+            | "ul"
+            | "ol"
+            | "hr"
+            | "article"
+            | "div"
+            | "main"
+            | "section"
+            | "blockquote"
+            | "table"
+            | "tr"
+    )
+}
+
+fn emphasise(text: &str, marker: &str) -> String {
+    // Markers must touch the text they emphasise.
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_owned();
+    }
+    let start = &text[..text.len() - text.trim_start().len()];
+    let end = &text[text.trim_end().len()..];
+    format!("{}{}{}{}{}", start, marker, trimmed, marker, end)
+}
+
+fn code_span(code: &str) -> String {
+    // The fence must be longer than any run of backticks inside it.
+    let longest = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest + 1);
+    let padding = match code.starts_with('`') || code.ends_with('`') {
+        true => " ",
+        false => "",
+    };
+    format!("{}{}{}{}{}", fence, padding, code, padding, fence)
+}
+
+/// Escapes characters that Markdown would otherwise treat as formatting.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        env,
+        fs::{read_dir, read_to_string, write},
+        path::Path,
+    };
+
+    /// Set to rewrite the expected Markdown from the current output.
+    const UPDATE_VAR: &str = "UPDATE_GOLDEN";
+
+    /// Each `testdata/<name>.html` is a saved puzzle page, and
+    /// `testdata/<name>.md` the README it should produce.
+    #[test]
+    fn golden() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata");
+        let mut pages = read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+            .collect::<Vec<_>>();
+        pages.sort();
+        assert!(!pages.is_empty());
+
+        for page in pages {
+            let name = page.file_stem().unwrap().to_str().unwrap();
+            let day = name.trim_start_matches(|c: char| !c.is_ascii_digit());
+            let url = format!("https://adventofcode.com/2021/day/{}", day);
+            let actual = instructions(&read_to_string(&page).unwrap(), &url);
+
+            let golden = page.with_extension("md");
+            if env::var_os(UPDATE_VAR).is_some() {
+                write(&golden, &actual).unwrap();
+                continue;
+            }
+            let expected = read_to_string(&golden).unwrap_or_else(|e| {
+                panic!(
+                    "{}: {}, set {} to create it",
+                    golden.display(),
+                    e,
+                    UPDATE_VAR
+                )
+            });
+            assert_eq!(
+                expected,
+                actual,
+                "{} differs from {}",
+                name,
+                golden.display()
+            );
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let page = r#"<p>Outside an article.</p><pre><code>0
+0</code></pre>
+<article><p>Flavour:</p><pre><code>Not an input</code></pre><p>For example:</p><pre><code>1
+2 &lt;3&gt;
+</code></pre><pre><code>1 (<em>increased</em>)
+2</code></pre><p>There are <code><em>2</em></code>, summed <code><em>1+2 = 3</em></code>.</p></article>
+<article><div><pre><code>4
+5</code></pre></div><p>Then <em><code>6</code></em>.</p></article>
+<article><p>No answer.</p></article>"#;
+
+        assert_eq!(
+            Examples {
+                inputs: vec!["1\n2 <3>\n".to_owned(), "4\n5".to_owned()],
+                answers: vec![Some("3".to_owned()), Some("6".to_owned()), None],
+            },
+            super::examples(page)
+        )
+    }
+
+    mod parse {
+        use super::*;
+
+        struct Case<'c> {
+            input: &'c str,
+            expected: Vec<Node>,
+        }
+
+        #[test]
+        fn unclosed() {
+            run(&Case {
+                input: "<p>a<em>b</p>c",
+                expected: vec![
+                    element("p", vec![text("a"), element("em", vec![text("b")])]),
+                    text("c"),
+                ],
+            })
+        }
+
+        #[test]
+        fn stray_closing_tag() {
+            run(&Case {
+                input: "a</em>b",
+                expected: vec![text("a"), text("b")],
+            })
+        }
+
+        #[test]
+        fn entities() {
+            run(&Case {
+                input: "&lt;&amp;&gt; &eacute;&#233;&#xE9; &nbsp;&hellip;",
+                expected: vec![text("<&> ééé \u{a0}…")],
+            })
+        }
+
+        #[test]
+        fn attributes() {
+            run(&Case {
+                input: r#"<a href='/2021' title="&quot;hi&quot;" hidden>x</a>"#,
+                expected: vec![Node::Element {
+                    name: "a".to_owned(),
+                    attrs: vec![
+                        ("href".to_owned(), "/2021".to_owned()),
+                        ("title".to_owned(), r#""hi""#.to_owned()),
+                        ("hidden".to_owned(), String::new()),
+                    ],
+                    children: vec![text("x")],
+                }],
+            })
+        }
+
+        #[test]
+        fn skipped() {
+            run(&Case {
+                input: "<!DOCTYPE html><!-- <p>x</p> --><script>if (a < b) {}</script>a<br/>",
+                expected: vec![text("a"), element("br", Vec::new())],
+            })
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, parse(test.input))
+        }
+
+        fn element(name: &str, children: Vec<Node>) -> Node {
+            Node::Element {
+                name: name.to_owned(),
+                attrs: Vec::new(),
+                children,
+            }
+        }
+
+        fn text(text: &str) -> Node {
+            Node::Text(text.to_owned())
+        }
+    }
+
+    mod url {
+        use super::*;
+
+        struct Case<'c> {
+            href: &'c str,
+            expected: &'c str,
+        }
+
+        #[test]
+        fn absolute() {
+            run(&Case {
+                href: "https://en.wikipedia.org/wiki/Transparency_(projection)",
+                expected: "https://en.wikipedia.org/wiki/Transparency_%28projection%29",
+            })
+        }
+
+        #[test]
+        fn root_relative() {
+            run(&Case {
+                href: "/2020/day/17",
+                expected: "https://adventofcode.com/2020/day/17",
+            })
+        }
+
+        #[test]
+        fn relative() {
+            run(&Case {
+                href: "17",
+                expected: "https://adventofcode.com/2021/day/17",
+            })
+        }
+
+        #[test]
+        fn fragment() {
+            run(&Case {
+                href: "#part2",
+                expected: "https://adventofcode.com/2021/day/19#part2",
+            })
+        }
+
+        #[test]
+        fn protocol_relative() {
+            run(&Case {
+                href: "//example.com/a b",
+                expected: "https://example.com/a%20b",
+            })
+        }
+
+        fn run(test: &Case) {
+            let renderer = Renderer {
+                page_url: "https://adventofcode.com/2021/day/19",
+            };
+            assert_eq!(test.expected, renderer.url(test.href))
+        }
+    }
+
+    #[test]
+    fn code_span_with_backticks() {
+        assert_eq!("`` `a` ``", code_span("`a`"))
+    }
+}
@@ -0,0 +1,407 @@
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// The Advent of Code website.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Where inputs are saved, relative to the repository root.
+pub const DATA_DIR: &str = "data";
+
+/// The event this repository solves, whose inputs, answers and examples are
+/// kept directly in [`DATA_DIR`] rather than in [`EVENTS_DIR`].
+pub const REPO_YEAR: i32 = 2021;
+
+/// Where other events' data is kept within [`DATA_DIR`], in a directory per
+/// year. It holds no inputs itself, so `aoc check` doesn't take it, or the
+/// years within it, for an input profile.
+pub const EVENTS_DIR: &str = "events";
+
+/// The first Advent of Code event.
+pub const FIRST_YEAR: i32 = 2015;
+
+/// A single day's puzzle from one event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: i32,
+    pub day: usize,
+}
+
+impl Puzzle {
+    /// Picks the puzzle to fetch, defaulting to the current event and, while
+    /// that event is running, the latest day.
+    pub fn new(year: Option<i32>, day: Option<usize>) -> Result<Self, PuzzleError> {
+        Self::at(year, day, Utc::now())
+    }
+
+    fn at(year: Option<i32>, day: Option<usize>, now: DateTime<Utc>) -> Result<Self, PuzzleError> {
+        let now = now.with_timezone(&est());
+        let current = current_year(now);
+        let year = year.unwrap_or(current);
+        if !(FIRST_YEAR..=current).contains(&year) {
+            return Err(PuzzleError::Year { year, current });
+        }
+
+        let last = last_day(year);
+        let day = match day {
+            Some(day) => day,
+            None if year == now.year() && now.month() == 12 => (now.day() as usize).min(last),
+            None => return Err(PuzzleError::NoDefaultDay { year }),
+        };
+        if !(1..=last).contains(&day) {
+            return Err(PuzzleError::Day { year, day, last });
+        }
+
+        Ok(Puzzle { year, day })
+    }
+
+    /// Picks the puzzle to wait for. Unlike [`Puzzle::new`] it may still be
+    /// locked, and it defaults to the next puzzle to unlock.
+    pub fn upcoming(year: Option<i32>, day: Option<usize>) -> Result<Self, PuzzleError> {
+        Self::upcoming_at(year, day, Utc::now())
+    }
+
+    fn upcoming_at(
+        year: Option<i32>,
+        day: Option<usize>,
+        now: DateTime<Utc>,
+    ) -> Result<Self, PuzzleError> {
+        let now = now.with_timezone(&est());
+        // Once an event's last puzzle is out, the next to unlock is a year away.
+        let next = match now.month() {
+            12 if now.day() as usize >= last_day(now.year()) => now.year() + 1,
+            _ => now.year(),
+        };
+        let year = year.unwrap_or(next);
+        if !(FIRST_YEAR..=next).contains(&year) {
+            return Err(PuzzleError::Year {
+                year,
+                current: next,
+            });
+        }
+
+        let last = last_day(year);
+        let day = match day {
+            Some(day) => day,
+            None if year == now.year() && now.month() == 12 => now.day() as usize + 1,
+            None if year == next => 1,
+            None => return Err(PuzzleError::NoDefaultDay { year }),
+        };
+        if !(1..=last).contains(&day) {
+            return Err(PuzzleError::Day { year, day, last });
+        }
+
+        Ok(Puzzle { year, day })
+    }
+
+    /// Puzzles unlock at midnight EST on their day of December.
+    pub fn unlocks_at(&self) -> DateTime<Utc> {
+        est()
+            .ymd(self.year, 12, self.day as u32)
+            .and_hms(0, 0, 0)
+            .with_timezone(&Utc)
+    }
+
+    pub fn url(&self) -> String {
+        self.url_at(BASE_URL)
+    }
+
+    /// The puzzle's page on a site at `base_url`, such as a stand-in for the
+    /// real one.
+    pub fn url_at(&self, base_url: &str) -> String {
+        format!(
+            "{}/{}/day/{}",
+            base_url.trim_end_matches('/'),
+            self.year,
+            self.day
+        )
+    }
+
+    /// Where the event's inputs, answers and examples are saved, relative to
+    /// the repository root: `data` for this repository's event, and e.g.
+    /// `data/events/2022` for others, so that the same day of two events
+    /// doesn't share an input.
+    pub fn data_dir(&self) -> PathBuf {
+        match self.year {
+            REPO_YEAR => PathBuf::from(DATA_DIR),
+            year => Path::new(DATA_DIR).join(EVENTS_DIR).join(year.to_string()),
+        }
+    }
+
+    /// Where the puzzle input is saved, relative to the repository root.
+    pub fn data_file(&self) -> PathBuf {
+        self.data_dir().join(self.data_file_name())
+    }
+
+    /// The input's file name, within [`Puzzle::data_dir`].
+    pub fn data_file_name(&self) -> String {
+        format!("day_{}.txt", self.day)
+    }
+}
+
+/// The time zone puzzles unlock in.
+fn est() -> FixedOffset {
+    FixedOffset::west(5 * 3600)
+}
+
+/// The latest event to have started by `now`.
+fn current_year(now: DateTime<FixedOffset>) -> i32 {
+    match now.month() {
+        12 => now.year(),
+        _ => now.year() - 1,
+    }
+}
+
+/// Events ran for 25 days until 2025, which had 12.
+pub fn last_day(year: i32) -> usize {
+    match year {
+        ..=2024 => 25,
+        _ => 12,
+    }
+}
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    Year { year: i32, current: i32 },
+    Day { year: i32, day: usize, last: usize },
+    NoDefaultDay { year: i32 },
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::Year { year, current } => write!(
+                f,
+                "no event in {}: events run from {} to {}",
+                year, FIRST_YEAR, current
+            ),
+            PuzzleError::Day { year, day, last } => {
+                write!(f, "no day {} in {}: days run from 1 to {}", day, year, last)
+            }
+            PuzzleError::NoDefaultDay { year } => write!(
+                f,
+                "the {} event is not running, so the day must be given",
+                year
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod at {
+        use super::*;
+
+        struct Case {
+            year: Option<i32>,
+            day: Option<usize>,
+            now: (i32, u32, u32),
+            expected: Option<Puzzle>,
+        }
+
+        #[test]
+        fn during_event() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2021, 12, 7),
+                expected: Some(Puzzle { year: 2021, day: 7 }),
+            })
+        }
+
+        #[test]
+        fn after_last_day() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2021, 12, 31),
+                expected: Some(Puzzle {
+                    year: 2021,
+                    day: 25,
+                }),
+            })
+        }
+
+        #[test]
+        fn short_event() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2025, 12, 20),
+                expected: Some(Puzzle {
+                    year: 2025,
+                    day: 12,
+                }),
+            })
+        }
+
+        #[test]
+        fn between_events() {
+            run(&Case {
+                year: None,
+                day: Some(3),
+                now: (2022, 6, 1),
+                expected: Some(Puzzle { year: 2021, day: 3 }),
+            })
+        }
+
+        #[test]
+        fn past_event() {
+            run(&Case {
+                year: Some(2015),
+                day: Some(25),
+                now: (2022, 12, 1),
+                expected: Some(Puzzle {
+                    year: 2015,
+                    day: 25,
+                }),
+            })
+        }
+
+        #[test]
+        fn no_default_day() {
+            run(&Case {
+                year: Some(2020),
+                day: None,
+                now: (2021, 12, 7),
+                expected: None,
+            })
+        }
+
+        #[test]
+        fn future_event() {
+            run(&Case {
+                year: Some(2022),
+                day: Some(1),
+                now: (2022, 11, 30),
+                expected: None,
+            })
+        }
+
+        #[test]
+        fn day_out_of_range() {
+            run(&Case {
+                year: Some(2025),
+                day: Some(13),
+                now: (2026, 1, 1),
+                expected: None,
+            })
+        }
+
+        fn run(test: &Case) {
+            let (year, month, day) = test.now;
+            // Noon UTC is the same date in EST.
+            let now = Utc.ymd(year, month, day).and_hms(12, 0, 0);
+
+            assert_eq!(test.expected, Puzzle::at(test.year, test.day, now).ok())
+        }
+    }
+
+    mod upcoming_at {
+        use super::*;
+
+        struct Case {
+            year: Option<i32>,
+            day: Option<usize>,
+            now: (i32, u32, u32, u32),
+            expected: Option<Puzzle>,
+        }
+
+        #[test]
+        fn before_midnight() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2021, 12, 6, 23),
+                expected: Some(Puzzle { year: 2021, day: 7 }),
+            })
+        }
+
+        #[test]
+        fn before_event() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2022, 11, 30, 23),
+                expected: Some(Puzzle { year: 2022, day: 1 }),
+            })
+        }
+
+        #[test]
+        fn after_last_day() {
+            run(&Case {
+                year: None,
+                day: None,
+                now: (2021, 12, 25, 12),
+                expected: Some(Puzzle { year: 2022, day: 1 }),
+            })
+        }
+
+        #[test]
+        fn unlocked() {
+            run(&Case {
+                year: Some(2021),
+                day: Some(3),
+                now: (2021, 12, 6, 23),
+                expected: Some(Puzzle { year: 2021, day: 3 }),
+            })
+        }
+
+        #[test]
+        fn too_far_ahead() {
+            run(&Case {
+                year: Some(2023),
+                day: Some(1),
+                now: (2022, 11, 30, 23),
+                expected: None,
+            })
+        }
+
+        fn run(test: &Case) {
+            let (year, month, day, hour) = test.now;
+            let now = est()
+                .ymd(year, month, day)
+                .and_hms(hour, 59, 59)
+                .with_timezone(&Utc);
+
+            assert_eq!(
+                test.expected,
+                Puzzle::upcoming_at(test.year, test.day, now).ok()
+            )
+        }
+    }
+
+    #[test]
+    fn unlocks_at() {
+        assert_eq!(
+            Utc.ymd(2021, 12, 7).and_hms(5, 0, 0),
+            Puzzle { year: 2021, day: 7 }.unlocks_at()
+        )
+    }
+
+    #[test]
+    fn data_file() {
+        assert_eq!(
+            Path::new("data/day_1.txt"),
+            Puzzle { year: 2021, day: 1 }.data_file()
+        );
+        assert_eq!(
+            Path::new("data/events/2022/day_1.txt"),
+            Puzzle { year: 2022, day: 1 }.data_file()
+        );
+    }
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            "https://adventofcode.com/2015/day/4",
+            Puzzle { year: 2015, day: 4 }.url()
+        )
+    }
+}
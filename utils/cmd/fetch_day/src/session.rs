@@ -0,0 +1,194 @@
+use std::{
+    env,
+    error::Error,
+    fmt,
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The environment variable the session token can be given in.
+pub const TOKEN_VAR: &str = "AOC_SESSION_TOKEN";
+
+/// Finds the session token, trying the `--token` flag, then [`TOKEN_VAR`],
+/// then the file from [`token_file`]. Empty values are skipped.
+pub fn token(flag: Option<&str>) -> Result<String, TokenError> {
+    resolve(
+        flag.map(str::to_owned),
+        env::var(TOKEN_VAR).ok(),
+        token_file().as_deref(),
+    )
+}
+
+/// `$XDG_CONFIG_HOME/aoc/token`, falling back to `~/.config/aoc/token`.
+pub fn token_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|config_dir| config_dir.join("aoc").join("token"))
+}
+
+fn resolve(
+    flag: Option<String>,
+    var: Option<String>,
+    file: Option<&Path>,
+) -> Result<String, TokenError> {
+    let given = [flag, var]
+        .into_iter()
+        .flatten()
+        .find(|token| !token.trim().is_empty());
+
+    let token = match (given, file) {
+        (Some(token), _) => Some(token),
+        (None, Some(file)) => match read_to_string(file) {
+            Ok(contents) => Some(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(source) => {
+                return Err(TokenError::Io {
+                    file: file.to_owned(),
+                    source,
+                })
+            }
+        },
+        (None, None) => None,
+    };
+
+    token
+        .map(|token| token.trim().to_owned())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| TokenError::Missing {
+            file: file.map(Path::to_owned),
+        })
+}
+
+/// Whether a page was served as if to a visitor who isn't logged in, which is
+/// what Advent of Code does when the session token is invalid or has expired.
+/// Such pages link to the login page in place of the puzzle input.
+pub fn is_logged_out(page: &str) -> bool {
+    page.contains("/auth/login")
+}
+
+#[derive(Debug)]
+pub enum TokenError {
+    Missing { file: Option<PathBuf> },
+    Io { file: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for TokenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenError::Missing { file } => {
+                write!(f, "no session token: pass --token or set {}", TOKEN_VAR)?;
+                match file {
+                    Some(file) => write!(f, ", or save it in {}", file.display()),
+                    None => Ok(()),
+                }
+            }
+            TokenError::Io { file, source } => write!(f, "{}: {}", file.display(), source),
+        }
+    }
+}
+
+impl Error for TokenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TokenError::Missing { .. } => None,
+            TokenError::Io { source, .. } => Some(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+
+    mod resolve {
+        use super::*;
+
+        struct Case<'c> {
+            flag: Option<&'c str>,
+            var: Option<&'c str>,
+            file: Option<&'c str>,
+            expected: Option<&'c str>,
+        }
+
+        #[test]
+        fn flag_first() {
+            run(&Case {
+                flag: Some("flag"),
+                var: Some("var"),
+                file: Some("file"),
+                expected: Some("flag"),
+            })
+        }
+
+        #[test]
+        fn var_before_file() {
+            run(&Case {
+                flag: None,
+                var: Some("var"),
+                file: Some("file"),
+                expected: Some("var"),
+            })
+        }
+
+        #[test]
+        fn file() {
+            run(&Case {
+                flag: None,
+                var: Some(""),
+                file: Some("file\n"),
+                expected: Some("file"),
+            })
+        }
+
+        #[test]
+        fn missing() {
+            run(&Case {
+                flag: None,
+                var: None,
+                file: Some("  \n"),
+                expected: None,
+            })
+        }
+
+        fn run(test: &Case) {
+            let dir = env::temp_dir().join(format!("fetch_day-token-{}", std::process::id()));
+            create_dir_all(&dir).unwrap();
+            let file = dir.join(format!("{:?}", test.expected));
+            if let Some(contents) = test.file {
+                write(&file, contents).unwrap();
+            }
+
+            let token = resolve(
+                test.flag.map(str::to_owned),
+                test.var.map(str::to_owned),
+                Some(&file),
+            );
+            match test.expected {
+                Some(expected) => assert_eq!(expected, token.unwrap()),
+                None => assert!(matches!(token, Err(TokenError::Missing { .. }))),
+            }
+        }
+    }
+
+    mod is_logged_out {
+        use super::*;
+
+        #[test]
+        fn logged_out() {
+            assert!(is_logged_out(
+                r#"<p>To begin, <a href="/2021/auth/login">get your puzzle input</a>.</p>"#
+            ))
+        }
+
+        #[test]
+        fn logged_in() {
+            assert!(!is_logged_out(
+                r#"<p>To begin, <a href="1/input" target="_blank">get your puzzle input</a>.</p>"#
+            ))
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Makes sense of the site's reply to a submitted answer, and records correct
+//! answers where the solutions' tests expect them.
+
+use crate::puzzle::Puzzle;
+use solution::{Answer, Answers, AnswersError, ANSWERS_FILE};
+use std::{
+    convert::Infallible,
+    fmt,
+    fs::{read_to_string, write},
+    io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The site's verdict on a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way.
+    Wrong,
+    /// An answer was submitted too recently. Another may be given after
+    /// `wait`, if the site said how long that is.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved, or isn't unlocked yet.
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooHigh => write!(f, "That's not the right answer: it's too high"),
+            Outcome::TooLow => write!(f, "That's not the right answer: it's too low"),
+            Outcome::Wrong => write!(f, "That's not the right answer"),
+            Outcome::RateLimited { wait: Some(wait) } => write!(
+                f,
+                "An answer was given too recently: wait {}s before trying again",
+                wait.as_secs()
+            ),
+            Outcome::RateLimited { wait: None } => write!(f, "An answer was given too recently"),
+            Outcome::AlreadySolved => write!(
+                f,
+                "That part is not the one being solved: it is already solved, or still locked"
+            ),
+        }
+    }
+}
+
+/// Classifies the page returned for a submitted answer, or `None` if it isn't
+/// recognised.
+pub fn classify(page: &str) -> Option<Outcome> {
+    let outcome = if page.contains("That's the right answer") {
+        Outcome::Correct
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else if page.contains("You gave an answer too recently") {
+        Outcome::RateLimited {
+            wait: wait_time(page),
+        }
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if page.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else {
+        return None;
+    };
+    Some(outcome)
+}
+
+/// Reads the wait from e.g. "You have 1m 5s left to wait."
+fn wait_time(page: &str) -> Option<Duration> {
+    let (_, rest) = page.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+
+    let mut secs = 0;
+    for part in wait.split_whitespace() {
+        let unit = match part.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        secs += part[..part.len() - 1].parse::<u64>().ok()? * unit;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Records a correct `answer` to `part` of the day's input in `data_dir`, in
+/// the directory's answers registry.
+pub fn record(data_dir: &Path, day: u8, part: u8, answer: &str) -> Result<PathBuf, AnswersError> {
+    let input_file = data_dir.join(format!("day_{}.txt", day));
+    let input = read_to_string(&input_file).map_err(|source| AnswersError::Io {
+        file: input_file,
+        source,
+    })?;
+
+    let answers_file = data_dir.join(ANSWERS_FILE);
+    let mut answers = match answers_file.exists() {
+        true => Answers::load(&answers_file)?,
+        false => Answers::default(),
+    };
+
+    let mut expected = answers.get(day, &input).cloned().unwrap_or_default();
+    let answer = answer
+        .parse::<Answer>()
+        .unwrap_or_else(|e: Infallible| match e {});
+    match part {
+        1 => expected.part_1 = answer,
+        _ => expected.part_2 = answer,
+    }
+    answers.insert(day, &input, expected);
+
+    answers.save(&answers_file)?;
+    Ok(answers_file)
+}
+
+/// Fills in the `part1Solution`/`part2Solution` constants of the Go and
+/// TypeScript tests, if they are still the templates' placeholder `0`,
+/// returning the files changed.
+pub fn fill_constants(puzzle: &Puzzle, part: u8, answer: &str) -> io::Result<Vec<PathBuf>> {
+    // Both languages take the constants as numbers.
+    if answer.parse::<i64>().is_err() {
+        return Ok(Vec::new());
+    }
+
+    let day_dir = format!("day_{}", puzzle.day);
+    let files = [
+        (Path::new("go").join(&day_dir).join("main_test.go"), "\n"),
+        (Path::new("ts").join(&day_dir).join("main.test.ts"), ";"),
+    ];
+
+    let mut changed = Vec::new();
+    for (file, terminator) in files {
+        if !file.exists() {
+            continue;
+        }
+        let placeholder = format!("part{}Solution = 0{}", part, terminator);
+        let contents = read_to_string(&file)?;
+        if contents.contains(&placeholder) {
+            let filled = format!("part{}Solution = {}{}", part, answer, terminator);
+            write(&file, contents.replacen(&placeholder, &filled, 1))?;
+            changed.push(file);
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solution::Expected;
+    use std::{
+        env,
+        fs::{create_dir_all, remove_dir_all},
+    };
+
+    mod classify {
+        use super::*;
+
+        struct Case<'c> {
+            page: &'c str,
+            expected: Option<Outcome>,
+        }
+
+        #[test]
+        fn correct() {
+            run(&Case {
+                page: "<article><p>That's the right answer! You are <em>one gold star</em> closer to saving your vacation.</p></article>",
+                expected: Some(Outcome::Correct),
+            })
+        }
+
+        #[test]
+        fn too_high() {
+            run(&Case {
+                page: "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2021/about\">about page</a>. Please wait one minute before trying again.</p></article>",
+                expected: Some(Outcome::TooHigh),
+            })
+        }
+
+        #[test]
+        fn too_low() {
+            run(&Case {
+                page:
+                    "<article><p>That's not the right answer; your answer is too low.</p></article>",
+                expected: Some(Outcome::TooLow),
+            })
+        }
+
+        #[test]
+        fn wrong() {
+            run(&Case {
+                page: "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>",
+                expected: Some(Outcome::Wrong),
+            })
+        }
+
+        #[test]
+        fn rate_limited() {
+            run(&Case {
+                page: "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2021/day/7\">[Return to Day 7]</a></p></article>",
+                expected: Some(Outcome::RateLimited {
+                    wait: Some(Duration::from_secs(65)),
+                }),
+            })
+        }
+
+        #[test]
+        fn already_solved() {
+            run(&Case {
+                page: "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2021/day/7\">[Return to Day 7]</a></p></article>",
+                expected: Some(Outcome::AlreadySolved),
+            })
+        }
+
+        #[test]
+        fn unrecognised() {
+            run(&Case {
+                page: "<html><body>Something else</body></html>",
+                expected: None,
+            })
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, classify(test.page))
+        }
+    }
+
+    #[test]
+    fn wait_time() {
+        assert_eq!(
+            Some(Duration::from_secs(3723)),
+            super::wait_time("You have 1h 2m 3s left to wait.")
+        );
+        assert_eq!(None, super::wait_time("You have a while left to wait."));
+    }
+
+    #[test]
+    fn record() {
+        let data_dir = env::temp_dir().join(format!("fetch_day-record-{}", std::process::id()));
+        create_dir_all(&data_dir).unwrap();
+        write(data_dir.join("day_7.txt"), "16,1,2\n").unwrap();
+
+        super::record(&data_dir, 7, 1, "37").unwrap();
+        super::record(&data_dir, 7, 2, "168").unwrap();
+
+        let expected = Answers::expected(&data_dir, 7);
+        remove_dir_all(&data_dir).unwrap();
+        assert_eq!(
+            Expected {
+                part_1: Answer::Integer(37),
+                part_2: Answer::Integer(168),
+            },
+            expected.unwrap()
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Scaffolds a day's code from the template sets in [`TEMPLATES_DIR`].
+//!
+//! Each directory in it is a language, named as the directory the language's
+//! days are kept in. Its files are copied into the new day's directory at the
+//! same relative paths, with `{{name}}` placeholders in both the paths and the
+//! contents replaced, and any `.tmpl` extension dropped. The extension keeps
+//! tools from treating the templates as code.
+
+use crate::changes::Changes;
+use std::{
+    error::Error,
+    fmt,
+    fs::{read_dir, read_to_string},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Where the template sets are kept, relative to the repository root.
+pub const TEMPLATES_DIR: &str = "templates";
+
+const EXTENSION: &str = "tmpl";
+
+/// The names of the template sets in `dir`, sorted.
+pub fn languages(dir: &Path) -> Result<Vec<String>, TemplateError> {
+    let io_error = |source| TemplateError::Io {
+        path: dir.to_owned(),
+        source,
+    };
+
+    let mut languages = Vec::new();
+    for entry in read_dir(dir).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        if entry.file_type().map_err(io_error)?.is_dir() {
+            languages.extend(entry.file_name().to_str().map(str::to_owned));
+        }
+    }
+    languages.sort();
+    Ok(languages)
+}
+
+/// Renders every file of the template set `set` into `out`. Files already in
+/// `out` are left alone, so scaffolding a solved day only adds what's missing.
+pub fn scaffold(
+    set: &Path,
+    out: &Path,
+    values: &[(&str, String)],
+    changes: &mut Changes,
+) -> Result<(), TemplateError> {
+    let mut templates = Vec::new();
+    find_files(set, &mut templates)?;
+
+    for template in templates {
+        let placeholder_error = |name: &str| TemplateError::Placeholder {
+            path: template.clone(),
+            name: name.to_owned(),
+        };
+        let io_error = |path: &Path| {
+            let path = path.to_owned();
+            move |source| TemplateError::Io { path, source }
+        };
+
+        let relative = template.strip_prefix(set).unwrap();
+        let relative = match relative.extension() {
+            Some(ext) if ext == EXTENSION => relative.with_extension(""),
+            _ => relative.to_owned(),
+        };
+        let file =
+            out.join(render(&relative.to_string_lossy(), values).map_err(placeholder_error)?);
+
+        let contents = read_to_string(&template).map_err(io_error(&template))?;
+        let contents = render(&contents, values).map_err(placeholder_error)?;
+
+        changes.create(&file, &contents).map_err(io_error(&file))?;
+    }
+    Ok(())
+}
+
+fn find_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), TemplateError> {
+    let io_error = |source| TemplateError::Io {
+        path: dir.to_owned(),
+        source,
+    };
+
+    let mut entries = read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            find_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Replaces each `{{name}}` in `template` with its value, failing with the
+/// first name without one. Braces around anything other than a name are left
+/// alone.
+fn render<'t>(template: &'t str, values: &[(&str, String)]) -> Result<String, &'t str> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let name = rest.find("}}").map(|end| &rest[..end]).filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+        match name {
+            Some(name) => {
+                let (_, value) = values.iter().find(|(key, _)| *key == name).ok_or(name)?;
+                rendered.push_str(value);
+                rest = &rest[name.len() + 2..];
+            }
+            None => rendered.push_str("{{"),
+        }
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+#[derive(Debug)]
+pub enum TemplateError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Placeholder {
+        path: PathBuf,
+        name: String,
+    },
+    UnknownLanguage {
+        lang: String,
+        available: Vec<String>,
+    },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            TemplateError::Placeholder { path, name } => {
+                write!(
+                    f,
+                    "{}: unknown placeholder '{{{{{}}}}}'",
+                    path.display(),
+                    name
+                )
+            }
+            TemplateError::UnknownLanguage { lang, available } => write!(
+                f,
+                "no templates for '{}' in {}/, only for: {}",
+                lang,
+                TEMPLATES_DIR,
+                available.join(", ")
+            ),
+        }
+    }
+}
+
+impl Error for TemplateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TemplateError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::changes::Change;
+    use std::{
+        env,
+        fs::{create_dir_all, remove_dir_all, remove_file, write},
+    };
+
+    mod render {
+        use super::*;
+
+        struct Case<'c> {
+            template: &'c str,
+            expected: Result<&'c str, &'c str>,
+        }
+
+        #[test]
+        fn placeholders() {
+            run(&Case {
+                template: "day_{{day}}: {{url}}#{{day}}",
+                expected: Ok("day_7: https://adventofcode.com/2021/day/7#7"),
+            })
+        }
+
+        #[test]
+        fn other_braces() {
+            run(&Case {
+                template: "fn f() {{}} {{ day }} {{Day}} {{day",
+                expected: Ok("fn f() {{}} {{ day }} {{Day}} {{day"),
+            })
+        }
+
+        #[test]
+        fn unknown() {
+            run(&Case {
+                template: "{{day}} {{data_dir}}",
+                expected: Err("data_dir"),
+            })
+        }
+
+        fn run(test: &Case) {
+            let values = [
+                ("day", "7".to_owned()),
+                ("url", "https://adventofcode.com/2021/day/7".to_owned()),
+            ];
+            assert_eq!(
+                test.expected.map(str::to_owned),
+                render(test.template, &values)
+            )
+        }
+    }
+
+    #[test]
+    fn scaffold() {
+        let dir = env::temp_dir().join(format!("fetch_day-template-{}", std::process::id()));
+        let (set, out) = (dir.join("templates").join("rs"), dir.join("day_7"));
+        create_dir_all(set.join("benches")).unwrap();
+        write(set.join("README.md"), "Day {{day}}").unwrap();
+        write(
+            set.join("benches").join("day_{{day}}.rs.tmpl"),
+            "// {{day}}",
+        )
+        .unwrap();
+        let values = [("day", "7".to_owned())];
+
+        let mut changes = Changes::new(false);
+        super::scaffold(&set, &out, &values, &mut changes).unwrap();
+        assert_eq!(
+            &[
+                Change::Create(out.join("README.md")),
+                Change::Create(out.join("benches").join("day_7.rs")),
+            ],
+            changes.made()
+        );
+        assert_eq!(
+            "// 7",
+            read_to_string(out.join("benches").join("day_7.rs")).unwrap()
+        );
+
+        // A solved day keeps its code.
+        write(out.join("README.md"), "Solved").unwrap();
+        remove_file(out.join("benches").join("day_7.rs")).unwrap();
+        let mut changes = Changes::new(false);
+        super::scaffold(&set, &out, &values, &mut changes).unwrap();
+        assert_eq!(
+            &[Change::Create(out.join("benches").join("day_7.rs"))],
+            changes.made()
+        );
+        assert_eq!("Solved", read_to_string(out.join("README.md")).unwrap());
+
+        assert_eq!(
+            vec!["rs".to_owned()],
+            languages(&dir.join("templates")).unwrap()
+        );
+
+        remove_dir_all(&dir).unwrap();
+    }
+}
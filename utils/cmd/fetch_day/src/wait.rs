@@ -0,0 +1,90 @@
+//! Waits for a puzzle to unlock, for `--wait`.
+
+use crate::client::FetchError;
+use chrono::{DateTime, Utc};
+use std::{
+    io::{stderr, Write},
+    thread::sleep,
+    time::Duration,
+};
+
+/// How many times to try fetching a puzzle that should have unlocked.
+const ATTEMPTS: u32 = 10;
+
+/// The longest to wait between attempts.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Sleeps until `unlock`, counting down to it on stderr.
+pub fn until(unlock: DateTime<Utc>) {
+    let mut stderr = stderr();
+    loop {
+        let left = match (unlock - Utc::now()).to_std() {
+            Ok(left) if !left.is_zero() => left,
+            // Negative: it has passed.
+            _ => break,
+        };
+        let _ = write!(stderr, "\rUnlocks in {}  ", countdown(left));
+        let _ = stderr.flush();
+        sleep(left.min(Duration::from_secs(1)));
+    }
+    let _ = writeln!(stderr, "\rUnlocked{}", " ".repeat(24));
+}
+
+/// Calls `fetch` until the puzzle is no longer locked, backing off between
+/// attempts. The site's clock may be behind ours, and it can take a moment to
+/// serve a new puzzle.
+pub fn retry<T>(mut fetch: impl FnMut() -> Result<T, FetchError>) -> Result<T, FetchError> {
+    let mut attempt = 1;
+    loop {
+        match fetch() {
+            Err(FetchError::Locked) if attempt < ATTEMPTS => {
+                let delay = backoff(attempt);
+                eprintln!("Still locked, trying again in {}s", delay.as_secs());
+                sleep(delay);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// The delay after the `attempt`th attempt: doubling from a second, up to
+/// [`MAX_DELAY`].
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(1 << (attempt - 1).min(16)).min(MAX_DELAY)
+}
+
+fn countdown(left: Duration) -> String {
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    match (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60) {
+        (0, 0, 0, s) => format!("{}s", s),
+        (0, 0, m, s) => format!("{}m {:02}s", m, s),
+        (0, h, m, s) => format!("{}h {:02}m {:02}s", h, m, s),
+        (d, h, m, s) => format!("{}d {:02}h {:02}m {:02}s", d, h, m, s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff() {
+        let delays = (1..=8)
+            .map(|attempt| super::backoff(attempt).as_secs())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![1, 2, 4, 8, 16, 32, 60, 60], delays);
+    }
+
+    #[test]
+    fn countdown() {
+        assert_eq!("1s", super::countdown(Duration::from_millis(300)));
+        assert_eq!("2m 05s", super::countdown(Duration::from_secs(125)));
+        assert_eq!("3h 00m 01s", super::countdown(Duration::from_secs(10801)));
+        assert_eq!(
+            "1d 02h 00m 01s",
+            super::countdown(Duration::from_secs(93601))
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! Registers a newly scaffolded day everywhere the workspace lists the days,
+//! so that it builds, runs and benchmarks without any manual wiring.
+//!
+//! Each registry is a list of one-line entries in day order. Registering a
+//! day already listed changes nothing, and a commented-out entry for the day
+//! is uncommented in place.
+
+use crate::changes::Changes;
+use std::{fs::read_to_string, io, path::Path};
+
+/// A list of per-day entries in one of the workspace's files.
+struct Registry {
+    file: &'static str,
+    /// The line opening the list.
+    start: &'static str,
+    /// The line closing the list. Entries are only looked for before it.
+    end: fn(&str) -> bool,
+    /// The day's entry, without indentation.
+    entry: fn(usize) -> String,
+}
+
+const REGISTRIES: &[Registry] = &[
+    Registry {
+        file: "Cargo.toml",
+        start: "members = [",
+        end: |line| line.trim() == "]",
+        entry: |day| format!(r#""rs/day_{}","#, day),
+    },
+    Registry {
+        file: "utils/cmd/aoc/Cargo.toml",
+        start: "[dependencies]",
+        end: |line| line.starts_with('['),
+        entry: dependency,
+    },
+    Registry {
+        file: "utils/cmd/aoc/Cargo.toml",
+        start: "embed-input = [",
+        end: |line| line.trim() == "]",
+        entry: feature,
+    },
+    Registry {
+        file: "utils/cmd/aoc/src/lib.rs",
+        start: "pub const SOLUTIONS",
+        end: |line| line.trim() == "];",
+        entry: |day| format!("&day_{0}::Day{0},", day),
+    },
+    Registry {
+        file: "utils/cmd/benchrs/Cargo.toml",
+        start: "[dependencies]",
+        end: |line| line.starts_with('['),
+        entry: dependency,
+    },
+    Registry {
+        file: "utils/cmd/benchrs/Cargo.toml",
+        start: "embed-input = [",
+        end: |line| line.trim() == "]",
+        entry: feature,
+    },
+    Registry {
+        file: "utils/cmd/benchrs/src/main.rs",
+        start: "fn run(",
+        end: |line| line.trim() == "_ => return None,",
+        entry: |day| format!("{0} => run_day!(day_{0}, opts),", day),
+    },
+];
+
+fn dependency(day: usize) -> String {
+    format!(r#"day_{0} = {{ path = "../../../rs/day_{0}" }}"#, day)
+}
+
+fn feature(day: usize) -> String {
+    format!(r#""day_{}/embed-input","#, day)
+}
+
+/// Adds `day` to every registry under `root`.
+pub fn register(root: &Path, day: usize, changes: &mut Changes) -> io::Result<()> {
+    let mut files = Vec::<(&Path, String)>::new();
+    for registry in REGISTRIES {
+        let file = Path::new(registry.file);
+        // Several registries may share a file.
+        let contents = match files.iter().position(|&(listed, _)| listed == file) {
+            Some(idx) => files.remove(idx).1,
+            None => read_to_string(root.join(file))?,
+        };
+        let registered = registry.register(&contents, day).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: no '{}' list", file.display(), registry.start),
+            )
+        })?;
+        files.push((file, registered));
+    }
+
+    for (file, registered) in files {
+        changes.write(&root.join(file), &registered)?;
+    }
+    Ok(())
+}
+
+impl Registry {
+    /// Returns `contents` with the day's entry added, or `None` if the list
+    /// can't be found.
+    fn register(&self, contents: &str, day: usize) -> Option<String> {
+        let mut lines = contents.lines().collect::<Vec<_>>();
+        let start = lines
+            .iter()
+            .position(|line| line.trim().starts_with(self.start))?
+            + 1;
+        let end = start
+            + lines[start..]
+                .iter()
+                .position(|line| (self.end)(line))
+                .unwrap_or(lines.len() - start);
+
+        // The listed days, with their line and whether they're commented out.
+        let entries = (start..end)
+            .filter_map(|idx| {
+                let line = lines[idx].trim_start();
+                let commented = line.starts_with('#') || line.starts_with("//");
+                entry_day(line).map(|listed| (idx, listed, commented))
+            })
+            .collect::<Vec<_>>();
+
+        if entries
+            .iter()
+            .any(|&(_, listed, commented)| listed == day && !commented)
+        {
+            return Some(contents.to_owned());
+        }
+
+        let indent = entries.first().map_or("    ", |&(idx, ..)| {
+            let line = lines[idx];
+            &line[..line.len() - line.trim_start().len()]
+        });
+        let entry = format!("{}{}", indent, (self.entry)(day));
+
+        match entries.iter().find(|&&(_, listed, _)| listed >= day) {
+            Some(&(idx, listed, _)) if listed == day => lines[idx] = &entry,
+            Some(&(idx, ..)) => lines.insert(idx, &entry),
+            None => {
+                // After the last entry, or at the end of the list if empty.
+                let idx = entries.last().map_or(end, |&(idx, ..)| idx + 1);
+                lines.insert(idx, &entry);
+            }
+        }
+
+        let mut registered = lines.join("\n");
+        if contents.ends_with('\n') {
+            registered.push('\n');
+        }
+        Some(registered)
+    }
+}
+
+/// The day named by the first `day_<N>` in `line`.
+fn entry_day(line: &str) -> Option<usize> {
+    let (_, rest) = line.split_once("day_")?;
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    rest[..digits].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod register {
+        use super::*;
+
+        struct Case<'c> {
+            registry: usize,
+            day: usize,
+            input: &'c str,
+            expected: &'c str,
+        }
+
+        #[test]
+        fn middle() {
+            run(&Case {
+                registry: 0,
+                day: 3,
+                input: "[workspace]\nmembers = [\n    \"utils/cmd/aoc\",\n    \"rs/day_2\",\n    \"rs/day_10\",\n]\n",
+                expected: "[workspace]\nmembers = [\n    \"utils/cmd/aoc\",\n    \"rs/day_2\",\n    \"rs/day_3\",\n    \"rs/day_10\",\n]\n",
+            })
+        }
+
+        #[test]
+        fn last() {
+            run(&Case {
+                registry: 3,
+                day: 25,
+                input: "pub const SOLUTIONS: &[&dyn DynSolution] = &[\n    &day_1::Day1,\n];\n\nconst OTHER: &[u8] = &[\n];\n",
+                expected: "pub const SOLUTIONS: &[&dyn DynSolution] = &[\n    &day_1::Day1,\n    &day_25::Day25,\n];\n\nconst OTHER: &[u8] = &[\n];\n",
+            })
+        }
+
+        #[test]
+        fn empty() {
+            run(&Case {
+                registry: 2,
+                day: 1,
+                input: "[features]\nembed-input = [\n]\n",
+                expected: "[features]\nembed-input = [\n    \"day_1/embed-input\",\n]\n",
+            })
+        }
+
+        #[test]
+        fn commented_out() {
+            run(&Case {
+                registry: 4,
+                day: 18,
+                input: "[dependencies]\nregex = \"1\"\nday_17 = { path = \"../../../rs/day_17\" }\n# day_18 = { path = \"../../../rs/day_18\" }\n\n[features]\nday_19 = []\n",
+                expected: "[dependencies]\nregex = \"1\"\nday_17 = { path = \"../../../rs/day_17\" }\nday_18 = { path = \"../../../rs/day_18\" }\n\n[features]\nday_19 = []\n",
+            })
+        }
+
+        #[test]
+        fn already_registered() {
+            let input = "fn run(day: u8, opts: &Opts) -> Option<Vec<Comparison>> {\n    let comparisons = match day {\n        1 => run_day!(day_1, opts),\n        // 2 => run_day!(day_2, opts),\n        _ => return None,\n    };\n    Some(comparisons)\n}";
+            run(&Case {
+                registry: 6,
+                day: 1,
+                input,
+                expected: input,
+            })
+        }
+
+        #[test]
+        fn match_arm() {
+            run(&Case {
+                registry: 6,
+                day: 2,
+                input: "fn run(day: u8, opts: &Opts) -> Option<Vec<Comparison>> {\n    let comparisons = match day {\n        1 => run_day!(day_1, opts),\n        // 2 => run_day!(day_2, opts),\n        _ => return None,\n    };\n    Some(comparisons)\n}",
+                expected: "fn run(day: u8, opts: &Opts) -> Option<Vec<Comparison>> {\n    let comparisons = match day {\n        1 => run_day!(day_1, opts),\n        2 => run_day!(day_2, opts),\n        _ => return None,\n    };\n    Some(comparisons)\n}",
+            })
+        }
+
+        fn run(test: &Case) {
+            let registry = &REGISTRIES[test.registry];
+            let registered = registry.register(test.input, test.day).unwrap();
+
+            assert_eq!(test.expected, registered);
+            assert_eq!(
+                registered,
+                registry.register(&registered, test.day).unwrap()
+            );
+        }
+    }
+
+    /// Every day in the repository is listed in every registry, including
+    /// days written before they were registered automatically.
+    #[test]
+    fn workspace() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../..");
+        let days = std::fs::read_dir(root.join("rs"))
+            .unwrap()
+            .filter_map(|entry| super::entry_day(&entry.unwrap().file_name().to_string_lossy()))
+            .collect::<Vec<_>>();
+        assert!(!days.is_empty());
+
+        for registry in REGISTRIES {
+            let contents = read_to_string(root.join(registry.file)).unwrap();
+            for &day in &days {
+                assert_eq!(
+                    contents,
+                    registry.register(&contents, day).unwrap(),
+                    "day {} is missing from {}",
+                    day,
+                    registry.file
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_list() {
+        assert_eq!(None, REGISTRIES[0].register("[workspace]\n", 1))
+    }
+
+    #[test]
+    fn entry_day() {
+        assert_eq!(
+            Some(22),
+            super::entry_day(r#"# day_22 = { path = "../../../rs/day_22" }"#)
+        );
+        assert_eq!(
+            None,
+            super::entry_day("solution = { path = \"../../lib/rs/solution\" }")
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::{borrow::Cow, convert::Infallible, fmt, str::FromStr};
+
+/// The answer to one part of a day's puzzle.
+///
+/// Integer answers compare equal regardless of the variant holding them, so
+/// `Integer(5) == BigInteger(5)`.
+#[derive(Debug, Default, Clone)]
+pub enum Answer {
+    Integer(i64),
+    BigInteger(i128),
+    String(Cow<'static, str>),
+    /// The puzzle has no answer for this part, e.g. day 25's part 2.
+    #[default]
+    Empty,
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match *self {
+            Answer::Integer(n) => Some(n as i128),
+            Answer::BigInteger(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::String(s) => write!(f, "{}", s),
+            Answer::Empty => write!(f, "-"),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::String(lhs), Answer::String(rhs)) => lhs == rhs,
+            (Answer::Empty, Answer::Empty) => true,
+            _ => matches!((self.as_i128(), other.as_i128()), (Some(lhs), Some(rhs)) if lhs == rhs),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Parses the [`Display`](fmt::Display) form of an answer back: `-` or an
+/// empty string is [`Answer::Empty`], integers use the smallest variant that
+/// fits, and anything else is a string.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() || s == "-" {
+            Ok(Answer::Empty)
+        } else if let Ok(n) = s.parse::<i64>() {
+            Ok(Answer::Integer(n))
+        } else if let Ok(n) = s.parse::<i128>() {
+            Ok(Answer::BigInteger(n))
+        } else {
+            Ok(Answer::String(Cow::Owned(s.to_owned())))
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Integer(n)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or(Answer::BigInteger(n), Answer::Integer)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::from(n as i128)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::from(n as i128)
+    }
+}
+
+impl From<&'static str> for Answer {
+    fn from(s: &'static str) -> Self {
+        Answer::String(Cow::Borrowed(s))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(Cow::Owned(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod eq {
+        use super::*;
+
+        struct Case {
+            lhs: Answer,
+            rhs: Answer,
+            expected: bool,
+        }
+
+        #[test]
+        fn integers() {
+            run(&Case {
+                lhs: Answer::Integer(42),
+                rhs: Answer::BigInteger(42),
+                expected: true,
+            })
+        }
+
+        #[test]
+        fn different_integers() {
+            run(&Case {
+                lhs: Answer::Integer(42),
+                rhs: Answer::Integer(41),
+                expected: false,
+            })
+        }
+
+        #[test]
+        fn strings() {
+            run(&Case {
+                lhs: Answer::from("abc"),
+                rhs: Answer::from(String::from("abc")),
+                expected: true,
+            })
+        }
+
+        #[test]
+        fn integer_and_string() {
+            run(&Case {
+                lhs: Answer::Integer(42),
+                rhs: Answer::from("42"),
+                expected: false,
+            })
+        }
+
+        #[test]
+        fn empty() {
+            run(&Case {
+                lhs: Answer::Empty,
+                rhs: Answer::Empty,
+                expected: true,
+            })
+        }
+
+        fn run(test: &Case) {
+            assert_eq!(test.expected, test.lhs == test.rhs);
+            assert_eq!(test.expected, test.rhs == test.lhs);
+        }
+    }
+
+    mod from_str {
+        use super::*;
+
+        struct Case<'c> {
+            input: &'c str,
+            expected: Answer,
+        }
+
+        #[test]
+        fn integer() {
+            run(&Case {
+                input: "1374",
+                expected: Answer::Integer(1374),
+            })
+        }
+
+        #[test]
+        fn big_integer() {
+            run(&Case {
+                input: "170141183460469231731687303715884105727",
+                expected: Answer::BigInteger(i128::MAX),
+            })
+        }
+
+        #[test]
+        fn string() {
+            run(&Case {
+                input: "uefzcucj",
+                expected: Answer::from("uefzcucj"),
+            })
+        }
+
+        #[test]
+        fn empty() {
+            run(&Case {
+                input: "-",
+                expected: Answer::Empty,
+            })
+        }
+
+        fn run(test: &Case) {
+            let answer = test.input.parse::<Answer>().unwrap();
+            assert_eq!(test.expected, answer);
+            assert_eq!(test.input, answer.to_string());
+        }
+    }
+
+    #[test]
+    fn from_unsigned() {
+        assert!(matches!(Answer::from(5usize), Answer::Integer(5)));
+        assert!(matches!(
+            Answer::from(u64::MAX),
+            Answer::BigInteger(n) if n == u64::MAX as i128
+        ));
+    }
+}
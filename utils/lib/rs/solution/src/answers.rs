@@ -0,0 +1,363 @@
+use crate::Answer;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    error::Error,
+    fmt,
+    fs::{read_to_string, write},
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The file name of the registry within a data directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The directory within a data directory holding the examples from each
+/// puzzle's description, as `day_N/<n>.txt`, with their own [`ANSWERS_FILE`].
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Written at the top of saved registries, as it can't be read back.
+const HEADER: &str = "# Expected answers, keyed by day and then by the hash of the (trimmed) input.
+# Answers are strings so that big integers round trip; \"-\" means no answer.
+";
+
+/// Registry of known puzzle answers, keyed by day and then by the
+/// [`input_hash`] of the input they answer, e.g.
+///
+/// ```toml
+/// [day_1.c05061b4db1c6d55]
+/// part_1 = "1374"
+/// part_2 = "1418"
+/// ```
+///
+/// Keying by input means several people's inputs can live side by side.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, Expected>>);
+
+/// The expected answers for one input.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Expected {
+    #[serde(default)]
+    pub part_1: Answer,
+    #[serde(default)]
+    pub part_2: Answer,
+}
+
+/// An example input from a puzzle's description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub expected: Expected,
+}
+
+impl Answers {
+    pub fn load(file: &Path) -> Result<Self, AnswersError> {
+        let contents = read_to_string(file).map_err(|source| AnswersError::Io {
+            file: file.to_owned(),
+            source,
+        })?;
+
+        toml::from_str(&contents).map_err(|source| AnswersError::Toml {
+            file: file.to_owned(),
+            source,
+        })
+    }
+
+    /// Loads the registry in `data_dir` and looks up the answers for that
+    /// directory's `day_N.txt`.
+    pub fn expected(data_dir: impl AsRef<Path>, day: u8) -> Result<Expected, AnswersError> {
+        let data_dir = data_dir.as_ref();
+        let (_, expected) = lookup(data_dir, &format!("day_{}.txt", day), day)?;
+        Ok(expected)
+    }
+
+    /// Loads the `n`th example of `day` from the [`EXAMPLES_DIR`] in
+    /// `data_dir`, with its answers.
+    pub fn example(data_dir: impl AsRef<Path>, day: u8, n: usize) -> Result<Example, AnswersError> {
+        let examples_dir = data_dir.as_ref().join(EXAMPLES_DIR);
+        let (input, expected) = lookup(&examples_dir, &format!("{}/{}.txt", day_key(day), n), day)?;
+        Ok(Example { input, expected })
+    }
+
+    /// Writes the registry to `file`, with the days in numeric order rather
+    /// than `day_10` before `day_2`.
+    pub fn save(&self, file: &Path) -> Result<(), AnswersError> {
+        let contents = self.to_toml().map_err(|source| AnswersError::Serialize {
+            file: file.to_owned(),
+            source,
+        })?;
+        write(file, contents).map_err(|source| AnswersError::Io {
+            file: file.to_owned(),
+            source,
+        })
+    }
+
+    /// The registry as [`save`](Answers::save) writes it.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        let mut days = self.0.iter().collect::<Vec<_>>();
+        days.sort_by_key(|(key, _)| (key.trim_start_matches("day_").parse::<u8>().ok(), *key));
+
+        let mut contents = HEADER.to_owned();
+        for (key, inputs) in days {
+            let day = toml::to_string(&BTreeMap::from([(key, inputs)]))?;
+            contents.push('\n');
+            contents.push_str(&day);
+        }
+        Ok(contents)
+    }
+
+    pub fn get(&self, day: u8, contents: &str) -> Option<&Expected> {
+        self.0
+            .get(&day_key(day))
+            .and_then(|inputs| inputs.get(&input_hash(contents)))
+    }
+
+    pub fn insert(&mut self, day: u8, contents: &str, expected: Expected) {
+        self.0
+            .entry(day_key(day))
+            .or_default()
+            .insert(input_hash(contents), expected);
+    }
+}
+
+/// Reads `input` from `dir`, and looks up its answers in the directory's
+/// registry.
+fn lookup(dir: &Path, input: &str, day: u8) -> Result<(String, Expected), AnswersError> {
+    let input_file = dir.join(input);
+    let contents = read_to_string(&input_file).map_err(|source| AnswersError::Io {
+        file: input_file.clone(),
+        source,
+    })?;
+
+    let answers_file = dir.join(ANSWERS_FILE);
+    let expected = Answers::load(&answers_file)?
+        .get(day, &contents)
+        .cloned()
+        .ok_or(AnswersError::Missing {
+            file: answers_file,
+            day,
+            hash: input_hash(&contents),
+        })?;
+    Ok((contents, expected))
+}
+
+fn day_key(day: u8) -> String {
+    format!("day_{}", day)
+}
+
+/// Identifies an input by a 64-bit FNV-1a hash of its trimmed contents, so
+/// trailing newlines don't change its identity.
+pub fn input_hash(contents: &str) -> String {
+    let hash = contents
+        .trim()
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        file: PathBuf,
+        source: io::Error,
+    },
+    Toml {
+        file: PathBuf,
+        source: toml::de::Error,
+    },
+    Serialize {
+        file: PathBuf,
+        source: toml::ser::Error,
+    },
+    Missing {
+        file: PathBuf,
+        day: u8,
+        hash: String,
+    },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { file, source } => write!(f, "{}: {}", file.display(), source),
+            AnswersError::Toml { file, source } => write!(f, "{}: {}", file.display(), source),
+            AnswersError::Serialize { file, source } => {
+                write!(f, "{}: {}", file.display(), source)
+            }
+            AnswersError::Missing { file, day, hash } => write!(
+                f,
+                "{}: no answers for [{}.{}]",
+                file.display(),
+                day_key(*day),
+                hash
+            ),
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Toml { source, .. } => Some(source),
+            AnswersError::Serialize { source, .. } => Some(source),
+            AnswersError::Missing { .. } => None,
+        }
+    }
+}
+
+/// Answers are stored as strings so that big integers survive the round trip
+/// through TOML.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let answer = String::deserialize(deserializer)?;
+        Ok(answer.parse().unwrap_or_else(|e: Infallible| match e {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+[day_1.af63ac4c86019afc]
+part_1 = "1374"
+part_2 = "1418"
+
+[day_25.af63ac4c86019afc]
+part_1 = "453"
+"#;
+
+    mod get {
+        use super::*;
+
+        struct Case<'c> {
+            day: u8,
+            contents: &'c str,
+            expected: Option<Expected>,
+        }
+
+        #[test]
+        fn known_input() {
+            run(&Case {
+                day: 1,
+                contents: "1",
+                expected: Some(Expected {
+                    part_1: Answer::Integer(1374),
+                    part_2: Answer::Integer(1418),
+                }),
+            })
+        }
+
+        #[test]
+        fn trailing_newline() {
+            run(&Case {
+                day: 1,
+                contents: "1\n",
+                expected: Some(Expected {
+                    part_1: Answer::Integer(1374),
+                    part_2: Answer::Integer(1418),
+                }),
+            })
+        }
+
+        #[test]
+        fn no_part_2() {
+            run(&Case {
+                day: 25,
+                contents: "1",
+                expected: Some(Expected {
+                    part_1: Answer::Integer(453),
+                    part_2: Answer::Empty,
+                }),
+            })
+        }
+
+        #[test]
+        fn unknown_input() {
+            run(&Case {
+                day: 1,
+                contents: "2",
+                expected: None,
+            })
+        }
+
+        #[test]
+        fn unknown_day() {
+            run(&Case {
+                day: 2,
+                contents: "1",
+                expected: None,
+            })
+        }
+
+        fn run(test: &Case) {
+            let answers: Answers = toml::from_str(ANSWERS).unwrap();
+            assert_eq!(test.expected.as_ref(), answers.get(test.day, test.contents))
+        }
+    }
+
+    #[test]
+    fn insert() {
+        let mut answers = Answers::default();
+        let expected = Expected {
+            part_1: Answer::Integer(1),
+            part_2: Answer::from("two"),
+        };
+
+        answers.insert(3, "input\n", expected.clone());
+
+        assert_eq!(Some(&expected), answers.get(3, "input"));
+    }
+
+    #[test]
+    fn example() {
+        let data_dir =
+            std::env::temp_dir().join(format!("solution-example-{}", std::process::id()));
+        let examples_dir = data_dir.join(EXAMPLES_DIR);
+        std::fs::create_dir_all(examples_dir.join("day_4")).unwrap();
+        write(examples_dir.join("day_4").join("2.txt"), "a\nb\n").unwrap();
+
+        let expected = Expected {
+            part_1: Answer::Integer(7),
+            part_2: Answer::Empty,
+        };
+        let mut answers = Answers::default();
+        answers.insert(4, "a\nb", expected.clone());
+        answers.save(&examples_dir.join(ANSWERS_FILE)).unwrap();
+
+        let example = Answers::example(&data_dir, 4, 2).unwrap();
+        std::fs::remove_dir_all(&data_dir).unwrap();
+        assert_eq!(
+            Example {
+                input: "a\nb\n".to_owned(),
+                expected,
+            },
+            example
+        );
+    }
+
+    #[test]
+    fn input_hash() {
+        assert_eq!("cbf29ce484222325", super::input_hash(""));
+        assert_eq!("af63ac4c86019afc", super::input_hash(" 1\n"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let answers: Answers = toml::from_str(ANSWERS).unwrap();
+        let reparsed: Answers = toml::from_str(&toml::to_string(&answers).unwrap()).unwrap();
+
+        assert_eq!(answers.0, reparsed.0);
+    }
+}
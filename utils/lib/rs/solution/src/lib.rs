@@ -0,0 +1,243 @@
+#![deny(clippy::all)]
+use std::{any::Any, fs::read_to_string, io::Read, ops::RangeInclusive, path::Path};
+
+mod answer;
+mod answers;
+mod parse;
+mod rng;
+
+pub use answer::Answer;
+pub use answers::{
+    input_hash, Answers, AnswersError, Example, Expected, ANSWERS_FILE, EXAMPLES_DIR,
+};
+pub use parse::{ParseError, Source};
+pub use rng::Rng;
+
+/// A single day's puzzle: how to parse its input and solve both parts.
+pub trait Solution {
+    type Input: 'static;
+
+    const DAY: u8;
+
+    /// The `size` at which [`Solution::generate`] matches the scale of the
+    /// real puzzle inputs.
+    const INPUT_SIZE: usize;
+
+    /// The sizes [`Solution::generate`] can make a valid input at.
+    const INPUT_SIZES: RangeInclusive<usize> = 1..=usize::MAX;
+
+    fn parse(&self, contents: &str) -> Result<Self::Input, ParseError>;
+
+    /// Reads and parses the input in `file`.
+    fn parse_file(&self, file: impl AsRef<Path>) -> Result<Self::Input, ParseError> {
+        let file = file.as_ref();
+        let contents = read_to_string(file).map_err(|e| ParseError::io(file, e))?;
+
+        self.parse(&contents).map_err(|e| e.in_file(file))
+    }
+
+    /// Reads `reader` to the end, e.g. stdin, and parses what was read.
+    fn parse_reader(&self, mut reader: impl Read) -> Result<Self::Input, ParseError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents)?;
+
+        self.parse(&contents)
+    }
+
+    /// The input compiled into the crate, if it was built with its `embed-input` feature.
+    fn embedded_input(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn part_1(&self, input: &Self::Input) -> Answer;
+
+    fn part_2(&self, input: &Self::Input) -> Answer;
+
+    /// Generates a random, valid input from `seed`, at a scale set by `size`.
+    /// What `size` counts, e.g. lines or the side of a grid, is up to the day,
+    /// and it must be one of [`Solution::INPUT_SIZES`].
+    fn generate(&self, seed: u64, size: usize) -> String;
+}
+
+/// Object-safe view of a [`Solution`], allowing days to be stored together,
+/// e.g. as a `&[&dyn DynSolution]` registry.
+pub trait DynSolution {
+    fn day(&self) -> u8;
+
+    fn parse(&self, contents: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    fn parse_file(&self, file: &Path) -> Result<Box<dyn Any>, ParseError>;
+
+    fn parse_reader(&self, reader: &mut dyn Read) -> Result<Box<dyn Any>, ParseError>;
+
+    fn embedded_input(&self) -> Option<&'static str>;
+
+    fn input_size(&self) -> usize;
+
+    fn input_sizes(&self) -> RangeInclusive<usize>;
+
+    fn generate(&self, seed: u64, size: usize) -> String;
+
+    /// Panics if `input` was not produced by this solution's `parse`.
+    fn part_1(&self, input: &dyn Any) -> Answer;
+
+    /// Panics if `input` was not produced by this solution's `parse`.
+    fn part_2(&self, input: &dyn Any) -> Answer;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, contents: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse(self, contents)?))
+    }
+
+    fn parse_file(&self, file: &Path) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse_file(self, file)?))
+    }
+
+    fn parse_reader(&self, reader: &mut dyn Read) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(Solution::parse_reader(self, reader)?))
+    }
+
+    fn embedded_input(&self) -> Option<&'static str> {
+        Solution::embedded_input(self)
+    }
+
+    fn input_size(&self) -> usize {
+        S::INPUT_SIZE
+    }
+
+    fn input_sizes(&self) -> RangeInclusive<usize> {
+        S::INPUT_SIZES
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        Solution::generate(self, seed, size)
+    }
+
+    fn part_1(&self, input: &dyn Any) -> Answer {
+        Solution::part_1(self, downcast::<S>(input))
+    }
+
+    fn part_2(&self, input: &dyn Any) -> Answer {
+        Solution::part_2(self, downcast::<S>(input))
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .unwrap_or_else(|| panic!("input was not parsed by day {}", S::DAY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Example;
+
+    impl Solution for Example {
+        type Input = Vec<usize>;
+
+        const DAY: u8 = 0;
+
+        const INPUT_SIZE: usize = 3;
+
+        fn parse(&self, contents: &str) -> Result<Self::Input, ParseError> {
+            let source = Source::new(contents);
+            contents.lines().map(|l| source.parse(l)).collect()
+        }
+
+        fn part_1(&self, input: &Self::Input) -> Answer {
+            input.iter().sum::<usize>().into()
+        }
+
+        fn part_2(&self, input: &Self::Input) -> Answer {
+            format!("{:?}", input).into()
+        }
+
+        fn generate(&self, seed: u64, size: usize) -> String {
+            let mut rng = Rng::new(seed);
+            (0..size).map(|_| format!("{}\n", rng.below(10))).collect()
+        }
+    }
+
+    mod parse_input {
+        use super::*;
+
+        #[test]
+        fn reader() {
+            let input = Solution::parse_reader(&Example, "1\n2\n3\n".as_bytes()).unwrap();
+
+            assert_eq!(vec![1, 2, 3], input);
+        }
+
+        #[test]
+        fn missing_file() {
+            let err = Solution::parse_file(&Example, "does/not/exist.txt").unwrap_err();
+
+            assert!(
+                matches!(err, ParseError::Io { file: Some(ref f), .. } if f == Path::new("does/not/exist.txt"))
+            );
+        }
+
+        #[test]
+        fn dyn_reader() {
+            let solutions: &[&dyn DynSolution] = &[&Example];
+
+            let input = solutions[0].parse_reader(&mut "4\n5".as_bytes()).unwrap();
+
+            assert_eq!(Answer::Integer(9), solutions[0].part_1(input.as_ref()));
+        }
+    }
+
+    mod dyn_solution {
+        use super::*;
+
+        #[test]
+        fn example() {
+            let solutions: &[&dyn DynSolution] = &[&Example];
+
+            let input = solutions[0].parse("1\n2\n3").unwrap();
+
+            assert_eq!(0, solutions[0].day());
+            assert_eq!(Answer::Integer(6), solutions[0].part_1(input.as_ref()));
+            assert_eq!(
+                Answer::from("[1, 2, 3]"),
+                solutions[0].part_2(input.as_ref())
+            );
+        }
+
+        #[test]
+        fn parse_error() {
+            let solutions: &[&dyn DynSolution] = &[&Example];
+
+            let err = solutions[0].parse("1\ntwo\n3").err().unwrap();
+
+            assert_eq!("2:1: expected usize, found \"two\"", err.to_string());
+        }
+
+        #[test]
+        fn generate() {
+            let solutions: &[&dyn DynSolution] = &[&Example];
+
+            let contents = solutions[0].generate(7, solutions[0].input_size());
+            let input = solutions[0].parse(&contents).unwrap();
+
+            assert_eq!(contents, solutions[0].generate(7, 3));
+            assert_eq!(3, input.downcast_ref::<Vec<usize>>().unwrap().len());
+            assert_eq!(1..=usize::MAX, solutions[0].input_sizes());
+        }
+
+        #[test]
+        #[should_panic(expected = "input was not parsed by day 0")]
+        fn wrong_input() {
+            let solutions: &[&dyn DynSolution] = &[&Example];
+
+            solutions[0].part_1(&"not an input");
+        }
+    }
+}
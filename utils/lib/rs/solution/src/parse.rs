@@ -0,0 +1,301 @@
+use std::{
+    any::type_name,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io {
+        file: Option<PathBuf>,
+        source: io::Error,
+    },
+    Syntax {
+        file: Option<PathBuf>,
+        line: usize,
+        column: usize,
+        expected: String,
+        found: String,
+    },
+}
+
+impl ParseError {
+    pub fn io(file: &Path, source: io::Error) -> Self {
+        Self::Io {
+            file: Some(file.to_owned()),
+            source,
+        }
+    }
+
+    /// Attributes the error to `file`, for errors raised while reading or parsing its contents.
+    pub fn in_file(self, file: &Path) -> Self {
+        match self {
+            Self::Io { source, .. } => Self::io(file, source),
+            Self::Syntax {
+                line,
+                column,
+                expected,
+                found,
+                ..
+            } => Self::Syntax {
+                file: Some(file.to_owned()),
+                line,
+                column,
+                expected,
+                found,
+            },
+        }
+    }
+
+    /// The 1-based line and column of a syntax error.
+    pub fn location(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Syntax { line, column, .. } => Some((*line, *column)),
+            Self::Io { .. } => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io {
+                file: Some(file),
+                source,
+            } => write!(f, "{}: {}", file.display(), source),
+            Self::Io { file: None, source } => write!(f, "could not read input: {}", source),
+            Self::Syntax {
+                file,
+                line,
+                column,
+                expected,
+                found,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file.display())?;
+                }
+                write!(f, "{}:{}: expected {}, ", line, column, expected)?;
+                if found.is_empty() {
+                    f.write_str("found end of input")
+                } else {
+                    write!(f, "found {:?}", found)
+                }
+            }
+        }
+    }
+}
+
+/// For errors reading input that doesn't come from a file, e.g. stdin.
+impl From<io::Error> for ParseError {
+    fn from(source: io::Error) -> Self {
+        Self::Io { file: None, source }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Syntax { .. } => None,
+        }
+    }
+}
+
+const MAX_FOUND_LEN: usize = 32;
+
+/// Puzzle input being parsed, used to locate the fragments that fail to parse.
+///
+/// Fragments passed to its methods must be slices of the contents it was created from,
+/// otherwise errors are reported at the start of the input. Create it from the untrimmed
+/// contents, and trim the slices being parsed, so that lines are counted from the start
+/// of the input.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    contents: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(contents: &'a str) -> Self {
+        Self { contents }
+    }
+
+    /// An empty fragment positioned after the last non-whitespace character, for reporting
+    /// missing input.
+    pub fn end(&self) -> &'a str {
+        &self.contents[self.contents.trim_end().len()..]
+    }
+
+    pub fn error(&self, fragment: &str, expected: impl Display) -> ParseError {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(self.contents.as_ptr() as usize)
+            .filter(|&offset| offset <= self.contents.len())
+            .unwrap_or(0);
+
+        let preceding = &self.contents[..offset];
+        let line_start = preceding.rfind('\n').map_or(0, |idx| idx + 1);
+
+        let found = match fragment.lines().next() {
+            Some(line) if line.chars().count() > MAX_FOUND_LEN => {
+                format!(
+                    "{}...",
+                    line.chars().take(MAX_FOUND_LEN).collect::<String>()
+                )
+            }
+            Some(line) => line.to_owned(),
+            None => String::new(),
+        };
+
+        ParseError::Syntax {
+            file: None,
+            line: preceding.matches('\n').count() + 1,
+            column: preceding[line_start..].chars().count() + 1,
+            expected: expected.to_string(),
+            found,
+        }
+    }
+
+    /// Parses `fragment`, reporting the target type as what was expected on failure.
+    pub fn parse<T: FromStr>(&self, fragment: &str) -> Result<T, ParseError> {
+        fragment
+            .parse::<T>()
+            .map_err(|_| self.error(fragment, type_name::<T>()))
+    }
+
+    pub fn split_once(
+        &self,
+        fragment: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        fragment
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(fragment, format!("{:?}", delimiter)))
+    }
+
+    /// Parses a single decimal digit.
+    pub fn digit(&self, fragment: &str) -> Result<u8, ParseError> {
+        match fragment.as_bytes().first() {
+            Some(&b) if b.is_ascii_digit() => Ok(b - b'0'),
+            _ => Err(self.error(fragment, "a digit")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod error {
+        use super::*;
+
+        struct Case<'c> {
+            contents: &'c str,
+            fragment: fn(&str) -> &str,
+            expected: &'c str,
+        }
+
+        #[test]
+        fn first_line() {
+            run(&Case {
+                contents: "abc\ndef",
+                fragment: |c| &c[1..],
+                expected: "1:2: expected something, found \"bc\"",
+            })
+        }
+
+        #[test]
+        fn later_line() {
+            run(&Case {
+                contents: "abc\ndef\nghi",
+                fragment: |c| &c[9..10],
+                expected: "3:2: expected something, found \"h\"",
+            })
+        }
+
+        #[test]
+        fn end_of_input() {
+            run(&Case {
+                contents: "abc\nde",
+                fragment: |c| Source::new(c).end(),
+                expected: "2:3: expected something, found end of input",
+            })
+        }
+
+        #[test]
+        fn end_of_input_before_trailing_whitespace() {
+            run(&Case {
+                contents: "abc\nde\n\n",
+                fragment: |c| Source::new(c).end(),
+                expected: "2:3: expected something, found end of input",
+            })
+        }
+
+        #[test]
+        fn leading_blank_lines() {
+            run(&Case {
+                contents: "\n\nabc\ndef\n",
+                fragment: |c| &c.trim()[5..],
+                expected: "4:2: expected something, found \"ef\"",
+            })
+        }
+
+        #[test]
+        fn unrelated_fragment() {
+            run(&Case {
+                contents: "abc\ndef",
+                fragment: |_| "xyz",
+                expected: "1:1: expected something, found \"xyz\"",
+            })
+        }
+
+        #[test]
+        fn long_fragment() {
+            run(&Case {
+                contents: "0123456789012345678901234567890123456789",
+                fragment: |c| c,
+                expected: "1:1: expected something, found \"01234567890123456789012345678901...\"",
+            })
+        }
+
+        fn run(test: &Case) {
+            let source = Source::new(test.contents);
+            assert_eq!(
+                test.expected,
+                source
+                    .error((test.fragment)(test.contents), "something")
+                    .to_string()
+            )
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let source = Source::new("12\n3x");
+
+        assert_eq!(12, source.parse::<usize>(&source.contents[..2]).unwrap());
+        assert_eq!(
+            "2:1: expected usize, found \"3x\"",
+            source
+                .parse::<usize>(&source.contents[3..])
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn in_file() {
+        let source = Source::new("forward 5\nup x");
+
+        let err = source
+            .split_once(&source.contents[10..], " -> ")
+            .unwrap_err()
+            .in_file(Path::new("data/day_2.txt"));
+
+        assert_eq!(Some((2, 1)), err.location());
+        assert_eq!(
+            "data/day_2.txt:2:1: expected \" -> \", found \"up x\"",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::ops::RangeInclusive;
+
+/// A small, seedable pseudo-random number generator (SplitMix64) for the input
+/// generators. The same seed always produces the same sequence, on every
+/// platform, so a generated input can be reproduced from its seed alone.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. Panics if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "cannot pick a number below 0");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in the inclusive `range`. Panics if the range is empty.
+    pub fn range(&mut self, range: RangeInclusive<isize>) -> isize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot pick from an empty range");
+        start + self.below(start.abs_diff(end) + 1) as isize
+    }
+
+    /// True with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random element of `items`. Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic() {
+        let (mut lhs, mut rhs) = (Rng::new(42), Rng::new(42));
+
+        for _ in 0..100 {
+            assert_eq!(lhs.next_u64(), rhs.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn range() {
+        let mut rng = Rng::new(0);
+        let mut seen = [false; 7];
+
+        for _ in 0..1000 {
+            let n = rng.range(-3..=3);
+            assert!((-3..=3).contains(&n));
+            seen[(n + 3) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn shuffle() {
+        let mut items = (0..100).collect::<Vec<_>>();
+
+        Rng::new(0).shuffle(&mut items);
+
+        assert_ne!((0..100).collect::<Vec<_>>(), items);
+        items.sort_unstable();
+        assert_eq!((0..100).collect::<Vec<_>>(), items);
+    }
+}